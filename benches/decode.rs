@@ -0,0 +1,36 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine as _;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use jwt_reader::{decode_jwt_payload, decode_jwt_payload_verbatim, decode_to_writer};
+
+// An access token with a typical mix of registered and custom claims
+fn sample_token() -> String {
+    let payload = r#"{"iss":"https://idp.example.com/","sub":"auth0|5f7c8ec7c33c6c004bbafe82","aud":["https://api.example.com","https://idp.example.com/userinfo"],"iat":1700000000,"exp":1700086400,"azp":"3xAmPl3cL13nT1d","scope":"openid profile email read:orders write:orders","permissions":["read:orders","write:orders","read:invoices"],"https://example.com/roles":["admin","billing"],"email":"jane.doe@example.com","email_verified":true}"#;
+    format!(
+        "eyJhbGciOiJSUzI1NiIsInR5cCI6IkpXVCIsImtpZCI6ImtleS0xIn0.{}.c2lnbmF0dXJl",
+        URL_SAFE_NO_PAD.encode(payload)
+    )
+}
+
+fn payload_decoding(c: &mut Criterion) {
+    let token = sample_token();
+    let mut group = c.benchmark_group("decode_payload");
+    group.bench_function("pretty", |b| {
+        b.iter(|| decode_jwt_payload(black_box(&token)).unwrap())
+    });
+    // Reuses one buffer across iterations, as a server writing into its response would.
+    let mut buffer = Vec::new();
+    group.bench_function("to_writer", |b| {
+        b.iter(|| {
+            buffer.clear();
+            decode_to_writer(black_box(&token), &mut buffer, true).unwrap();
+        })
+    });
+    group.bench_function("verbatim", |b| {
+        b.iter(|| decode_jwt_payload_verbatim(black_box(&token)).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, payload_decoding);
+criterion_main!(benches);
@@ -0,0 +1,25 @@
+use std::fs;
+use std::process::Command;
+
+// Embeds the short commit hash as JWT_READER_GIT_HASH for `jwt_reader --version`. Builds
+// outside a git checkout, e.g. from a published crate, simply go without it.
+fn main() {
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    if let Ok(head) = fs::read_to_string(".git/HEAD") {
+        if let Some(branch) = head.trim().strip_prefix("ref: ") {
+            println!("cargo:rerun-if-changed=.git/{}", branch);
+        }
+    }
+
+    let hash = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|hash| hash.trim().to_string())
+        .filter(|hash| !hash.is_empty());
+    if let Some(hash) = hash {
+        println!("cargo:rustc-env=JWT_READER_GIT_HASH={}", hash);
+    }
+}
@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{decode_jwt_claims, JwtError};
+
+// The `aud` claim, which RFC 7519 allows to be a single string or an array of strings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Audience {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl Audience {
+    /// Returns the audiences as a list, whichever form the claim used.
+    pub fn as_vec(&self) -> Vec<&str> {
+        match self {
+            Audience::Single(aud) => vec![aud.as_str()],
+            Audience::Multiple(auds) => auds.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+// The registered claims of RFC 7519 section 4.1, with every other claim kept in `extra`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StandardClaims {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aud: Option<Audience>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exp: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nbf: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iat: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jti: Option<String>,
+    // Custom claims, in the order the issuer encoded them
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Decodes the payload of a JWT into its typed registered claims.
+///
+/// # Arguments
+/// * `token_str` - A string slice representing the JWT.
+///
+/// # Returns
+/// A `Result` containing the `StandardClaims`, or a `JwtError` (`JsonParseError` when the
+/// payload is not an object or a registered claim has the wrong type, e.g. a string `exp`).
+///
+/// # Remarks
+/// This function does NOT verify the JWT's signature.
+pub fn decode_standard_claims(token_str: &str) -> Result<StandardClaims, JwtError> {
+    Ok(serde_json::from_value(decode_jwt_claims(token_str)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encode_unsigned;
+    use serde_json::json;
+
+    #[test]
+    fn test_registered_and_custom_claims() {
+        let payload = json!({
+            "iss": "https://idp.example.com/",
+            "sub": "1234567890",
+            "aud": ["api", "web"],
+            "exp": 1701502400,
+            "iat": 1701498800,
+            "scope": "read",
+            "roles": ["admin"],
+        });
+        let token = encode_unsigned(&json!({"alg": "none"}), &payload).unwrap();
+        let claims = decode_standard_claims(&token).unwrap();
+
+        assert_eq!(claims.iss.as_deref(), Some("https://idp.example.com/"));
+        assert_eq!(claims.sub.as_deref(), Some("1234567890"));
+        assert_eq!(claims.aud.unwrap().as_vec(), ["api", "web"]);
+        assert_eq!(claims.exp, Some(1701502400));
+        assert_eq!(claims.nbf, None);
+        assert_eq!(claims.jti, None);
+        assert_eq!(
+            Value::Object(claims.extra),
+            json!({"scope": "read", "roles": ["admin"]})
+        );
+    }
+
+    #[test]
+    fn test_single_audience_and_wrong_types() {
+        let token = encode_unsigned(&json!({"alg": "none"}), &json!({"aud": "api"})).unwrap();
+        assert_eq!(
+            decode_standard_claims(&token).unwrap().aud,
+            Some(Audience::Single("api".to_string()))
+        );
+
+        let token = encode_unsigned(&json!({"alg": "none"}), &json!({"exp": "soon"})).unwrap();
+        assert_eq!(
+            decode_standard_claims(&token).unwrap_err(),
+            JwtError::JsonParseError(
+                serde_json::from_value::<StandardClaims>(json!({"exp": "soon"})).unwrap_err()
+            )
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A source of the current time for the time-validation functions, so that tests can pin
+/// "now" instead of depending on the real clock.
+pub trait Clock {
+    /// Returns the current time as whole seconds since the Unix epoch.
+    fn now_unix(&self) -> i64;
+}
+
+// The real system clock
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_unix(&self) -> i64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs() as i64)
+            .unwrap_or(0)
+    }
+}
+
+// A clock stopped at the given number of seconds since the Unix epoch, for tests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedClock(pub i64);
+
+impl Clock for FixedClock {
+    fn now_unix(&self) -> i64 {
+        self.0
+    }
+}
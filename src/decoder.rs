@@ -0,0 +1,438 @@
+use std::time::{Duration, Instant};
+
+use serde::de::IgnoredAny;
+use serde_json::{from_str, Value};
+
+use crate::parts::{split_token, strip_bearer_prefix};
+use crate::{
+    check_token_len, render_value, Base64Policy, JsonStyle, JwtError, OutputFormat, TokenPart,
+    DEFAULT_MAX_TOKEN_LEN,
+};
+
+// Decoding settings that are set up once and reused for every token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JwtDecoder {
+    // Accepted Base64 encodings
+    pub base64: Base64Policy,
+    // Output format used by `render`
+    pub format: OutputFormat,
+    // JSON layout used by `render`
+    pub style: JsonStyle,
+    // Longest accepted token in bytes, or `None` for no limit
+    pub max_len: Option<usize>,
+}
+
+impl Default for JwtDecoder {
+    fn default() -> Self {
+        JwtDecoder {
+            max_len: Some(DEFAULT_MAX_TOKEN_LEN),
+            ..UNLIMITED_DECODER
+        }
+    }
+}
+
+// How long each stage of decoding one payload took
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DecodeTimings {
+    // Splitting the token and Base64-decoding (and, with `zip`, inflating) the payload
+    pub base64: Duration,
+    // Checking that the payload bytes are UTF-8
+    pub utf8: Duration,
+    // Parsing the payload text as JSON
+    pub json: Duration,
+}
+
+// Decoder behind the free `decode_*` functions, which leave size limits to the caller
+pub(crate) const UNLIMITED_DECODER: JwtDecoder = JwtDecoder {
+    base64: Base64Policy::Lenient,
+    format: OutputFormat::Json,
+    style: JsonStyle::Pretty,
+    max_len: None,
+};
+
+impl JwtDecoder {
+    /// Checks the token against `max_len`.
+    fn check_len(&self, token_str: &str) -> Result<(), JwtError> {
+        match self.max_len {
+            Some(limit) => check_token_len(token_str, limit),
+            None => Ok(()),
+        }
+    }
+
+    /// Rejects an empty part, such as the payload of `header..signature`, before decoding it
+    /// to zero bytes would surface as an unhelpful JSON parsing error.
+    fn require_part(part: TokenPart, part_encoded: &str) -> Result<(), JwtError> {
+        if part_encoded.is_empty() {
+            return Err(JwtError::InvalidTokenFormat(format!(
+                "Token has an empty {} part.",
+                part
+            )));
+        }
+        Ok(())
+    }
+
+    /// Decodes a single Base64 encoded JWT part and parses it as JSON.
+    pub(crate) fn decode_part(
+        &self,
+        part: TokenPart,
+        part_encoded: &str,
+    ) -> Result<Value, JwtError> {
+        Self::require_part(part, part_encoded)?;
+        let part_decoded_bytes = self.base64.decode_token_part(part, part_encoded)?;
+        let part_json_str = String::from_utf8(part_decoded_bytes)?;
+        Ok(from_str(&part_json_str)?)
+    }
+
+    /// Decodes a Base64 encoded payload part like `decode_part`, first inflating it when the
+    /// given header part asks for it.
+    pub(crate) fn decode_payload_part(
+        &self,
+        header_encoded: &str,
+        payload_encoded: &str,
+    ) -> Result<Value, JwtError> {
+        Self::require_part(TokenPart::Payload, payload_encoded)?;
+        let payload = self
+            .base64
+            .decode_token_part(TokenPart::Payload, payload_encoded)?;
+        let payload_str = String::from_utf8(self.inflate(header_encoded, payload)?)?;
+        Ok(from_str(&payload_str)?)
+    }
+
+    /// Decodes the payload part of a JWT to bytes, inflating a compressed payload.
+    fn payload_bytes(&self, token_str: &str) -> Result<Vec<u8>, JwtError> {
+        self.check_len(token_str)
+            .inspect_err(|e| error!("Rejected token before decoding: {}", e))?;
+        let (header_encoded, payload_encoded, signature_encoded) =
+            split_token(token_str).inspect_err(|e| error!("Splitting the token failed: {}", e))?;
+        trace!(
+            "Split token into header ({} bytes), payload ({} bytes) and signature ({} bytes)",
+            header_encoded.len(),
+            payload_encoded.len(),
+            signature_encoded.map_or(0, str::len)
+        );
+        Self::require_part(TokenPart::Payload, payload_encoded)
+            .inspect_err(|e| error!("Rejected token before decoding: {}", e))?;
+        let payload = self
+            .base64
+            .decode_token_part(TokenPart::Payload, payload_encoded)
+            .inspect_err(|e| error!("Base64 decoding of the payload failed: {}", e))?;
+        trace!("Base64-decoded the payload to {} bytes", payload.len());
+        self.inflate(header_encoded, payload)
+    }
+
+    /// Checks that the payload bytes are UTF-8 text.
+    fn payload_text(&self, token_str: &str) -> Result<String, JwtError> {
+        let payload_str = String::from_utf8(self.payload_bytes(token_str)?)
+            .inspect_err(|e| error!("The payload is not valid UTF-8: {}", e))?;
+        trace!("The payload is valid UTF-8");
+        Ok(payload_str)
+    }
+
+    /// Inflates the payload if the header sets `zip` to `"DEF"` (RFC 7516 section 4.1.3).
+    ///
+    /// # Returns
+    /// The payload, inflated or as it was, or `JwtError::DecompressionError` when it cannot be
+    /// inflated or `zip` names an unknown algorithm.
+    ///
+    /// # Remarks
+    /// A header that cannot be decoded is treated as having no `zip`, so the payload can still
+    /// be read from tokens with a damaged header.
+    #[cfg(feature = "compression")]
+    fn inflate(&self, header_encoded: &str, payload: Vec<u8>) -> Result<Vec<u8>, JwtError> {
+        use std::io::Read;
+
+        let header = self.decode_part(TokenPart::Header, header_encoded).ok();
+        match header.as_ref().and_then(|header| header.get("zip")) {
+            None => Ok(payload),
+            Some(Value::String(zip)) if zip == "none" => Ok(payload),
+            Some(Value::String(zip)) if zip == "DEF" => {
+                let mut inflated = Vec::new();
+                flate2::read::DeflateDecoder::new(payload.as_slice())
+                    .read_to_end(&mut inflated)
+                    .map_err(|e| JwtError::DecompressionError(e.to_string()))?;
+                Ok(inflated)
+            }
+            Some(zip) => Err(JwtError::DecompressionError(format!(
+                "Unsupported zip algorithm: {}",
+                zip
+            ))),
+        }
+    }
+
+    #[cfg(not(feature = "compression"))]
+    fn inflate(&self, _header_encoded: &str, payload: Vec<u8>) -> Result<Vec<u8>, JwtError> {
+        Ok(payload)
+    }
+
+    /// Decodes the payload of a JWT and returns the parsed claims.
+    ///
+    /// # Arguments
+    /// * `token_str` - A string slice representing the JWT.
+    ///
+    /// # Returns
+    /// A `Result` containing the payload as a `serde_json::Value` or a `JwtError`
+    /// (`TokenTooLarge` for tokens longer than `max_len`).
+    ///
+    /// # Remarks
+    /// This function does NOT verify the JWT's signature.
+    pub fn decode(&self, token_str: &str) -> Result<Value, JwtError> {
+        let payload_json_str = self.payload_text(token_str)?;
+        let claims: Value = from_str(&payload_json_str)
+            .inspect_err(|e| error!("Parsing the payload as JSON failed: {}", e))?;
+        debug!(
+            "Decoded the payload JSON ({} bytes)",
+            payload_json_str.len()
+        );
+        Ok(claims)
+    }
+
+    /// Decodes the payload of a JWT like `decode`, measuring how long each stage takes.
+    ///
+    /// # Arguments
+    /// * `token_str` - A string slice representing the JWT.
+    ///
+    /// # Returns
+    /// A `Result` containing the claims and the `DecodeTimings` of the Base64, UTF-8 and JSON
+    /// stages, or the `JwtError` that `decode` would return.
+    ///
+    /// # Remarks
+    /// This function does NOT verify the JWT's signature.
+    pub fn decode_timed(&self, token_str: &str) -> Result<(Value, DecodeTimings), JwtError> {
+        let started = Instant::now();
+        let payload = self.payload_bytes(token_str)?;
+        let base64 = started.elapsed();
+
+        let started = Instant::now();
+        let payload_str = String::from_utf8(payload)?;
+        let utf8 = started.elapsed();
+
+        let started = Instant::now();
+        let claims: Value = from_str(&payload_str)?;
+        let json = started.elapsed();
+
+        Ok((claims, DecodeTimings { base64, utf8, json }))
+    }
+
+    /// Decodes the header of a JWT and returns the parsed JSON value.
+    ///
+    /// # Arguments
+    /// * `token_str` - A string slice representing the JWT.
+    ///
+    /// # Returns
+    /// A `Result` containing the header as a `serde_json::Value` or a `JwtError`
+    /// (`TokenTooLarge` for tokens longer than `max_len`).
+    ///
+    /// # Remarks
+    /// This function does NOT verify the JWT's signature.
+    pub fn decode_header(&self, token_str: &str) -> Result<Value, JwtError> {
+        self.check_len(token_str)?;
+        // The header is the first of the dot-separated parts. It is taken directly rather than
+        // through `split_token` so that the protected header of a JWE can still be inspected.
+        let header_encoded = match strip_bearer_prefix(token_str).split('.').next() {
+            Some(header) if !header.is_empty() => header,
+            _ => {
+                return Err(JwtError::InvalidTokenFormat(
+                    "Token does not contain a header part.".to_string(),
+                ))
+            }
+        };
+
+        self.decode_part(TokenPart::Header, header_encoded)
+    }
+
+    /// Decodes the payload of a JWT to its raw bytes, inflated if compressed, without UTF-8
+    /// or JSON parsing.
+    ///
+    /// # Arguments
+    /// * `token_str` - A string slice representing the JWT.
+    ///
+    /// # Returns
+    /// A `Result` containing the Base64-decoded payload bytes or a `JwtError`
+    /// (`TokenTooLarge` for tokens longer than `max_len`).
+    ///
+    /// # Remarks
+    /// This function does NOT verify the JWT's signature.
+    pub fn decode_raw(&self, token_str: &str) -> Result<Vec<u8>, JwtError> {
+        self.check_len(token_str)?;
+        let (header_encoded, payload_encoded, _) = split_token(token_str)?;
+        let payload = self
+            .base64
+            .decode_token_part(TokenPart::Payload, payload_encoded)?;
+        self.inflate(header_encoded, payload)
+    }
+
+    /// Decodes the payload of a JWT and returns its JSON text exactly as the issuer encoded it.
+    ///
+    /// # Arguments
+    /// * `token_str` - A string slice representing the JWT.
+    ///
+    /// # Returns
+    /// A `Result` containing the payload text or a `JwtError` (`JsonParseError` when the
+    /// payload is not well-formed JSON, `TokenTooLarge` for tokens longer than `max_len`).
+    ///
+    /// # Remarks
+    /// The JSON is checked without building a `Value` and is never re-serialized, which makes
+    /// this considerably cheaper than `render`. `format` and `style` are ignored.
+    /// This function does NOT verify the JWT's signature.
+    pub fn decode_verbatim(&self, token_str: &str) -> Result<String, JwtError> {
+        let payload_str = self.payload_text(token_str)?;
+        from_str::<IgnoredAny>(&payload_str)
+            .inspect_err(|e| error!("Parsing the payload as JSON failed: {}", e))?;
+        debug!("Validated the payload JSON ({} bytes)", payload_str.len());
+        Ok(payload_str)
+    }
+
+    /// Decodes the payload of a JWT and serializes it in the configured format and style.
+    ///
+    /// # Arguments
+    /// * `token_str` - A string slice representing the JWT.
+    ///
+    /// # Returns
+    /// A `Result` containing the rendered payload or a `JwtError`.
+    ///
+    /// # Remarks
+    /// This function does NOT verify the JWT's signature.
+    pub fn render(&self, token_str: &str) -> Result<String, JwtError> {
+        let claims = self.decode(token_str)?;
+        render_value(&claims, self.format, self.style)
+    }
+}
+
+// Builds a token whose header is {"alg":"HS256","zip":"DEF"} around the deflated payload JSON.
+#[cfg(all(test, feature = "compression"))]
+pub(crate) fn deflated_token(payload: &str) -> String {
+    use std::io::Write;
+
+    let mut encoder =
+        flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(payload.as_bytes()).unwrap();
+    format!(
+        "eyJhbGciOiJIUzI1NiIsInppcCI6IkRFRiJ9.{}.c2ln",
+        crate::parts::b64url_encode(&encoder.finish().unwrap())
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::engine::DecodePaddingMode;
+
+    #[test]
+    fn test_decode_timed_matches_decode() {
+        let decoder = JwtDecoder::default();
+        let token = "eyJhbGciOiJIUzI1NiJ9.eyJzdWIiOiIxMjM0NTY3ODkwIn0.c2ln";
+        let (claims, _timings) = decoder.decode_timed(token).unwrap();
+        assert_eq!(claims, decoder.decode(token).unwrap());
+
+        for bad in [
+            "eyJhbGciOiJIUzI1NiJ9",
+            "eyJhbGciOiJIUzI1NiJ9.bm90IGpzb24.c2ln",
+        ] {
+            assert_eq!(
+                decoder.decode_timed(bad).unwrap_err(),
+                decoder.decode(bad).unwrap_err()
+            );
+        }
+    }
+
+    #[test]
+    fn test_decoder_reuses_settings() {
+        let decoder = JwtDecoder {
+            format: OutputFormat::Yaml,
+            ..JwtDecoder::default()
+        };
+        for (token, sub) in [
+            ("eyJhbGciOiJIUzI1NiJ9.eyJzdWIiOiJhIn0.c2ln", "a"),
+            ("eyJhbGciOiJIUzI1NiJ9.eyJzdWIiOiJiIn0.c2ln", "b"),
+        ] {
+            assert_eq!(decoder.render(token).unwrap(), format!("sub: {}", sub));
+        }
+    }
+
+    #[test]
+    fn test_url_safe_only_policy() {
+        // Payload {"name":"Jürgen?>"} in standard Base64, which contains '+' and '=' padding.
+        let token = "eyJhbGciOiJIUzI1NiJ9.eyJuYW1lIjoiSsO8cmdlbj8+In0=.c2lnbmF0dXJl";
+        assert!(JwtDecoder::default().decode(token).is_ok());
+
+        let strict = JwtDecoder {
+            base64: Base64Policy::UrlSafeOnly,
+            ..JwtDecoder::default()
+        };
+        assert_eq!(
+            strict.decode(token).unwrap_err(),
+            JwtError::Base64DecodeError {
+                part: TokenPart::Payload,
+                error: base64::DecodeError::InvalidByte(23, b'+'),
+            }
+        );
+    }
+
+    #[test]
+    fn test_custom_alphabet() {
+        // Same payload as above, in standard Base64 with its '=' padding kept.
+        let token = "eyJhbGciOiJIUzI1NiJ9.eyJuYW1lIjoiSsO8cmdlbj8+In0=.c2lnbmF0dXJl";
+        let decoder = JwtDecoder {
+            base64: Base64Policy::Custom {
+                alphabet: base64::alphabet::STANDARD,
+                padding: DecodePaddingMode::RequireCanonical,
+            },
+            ..JwtDecoder::default()
+        };
+        assert_eq!(decoder.decode(token).unwrap()["name"], "Jürgen?>");
+
+        // Canonical padding rejects the unpadded encoding conforming tokens use.
+        assert_eq!(
+            decoder
+                .decode("eyJhbGciOiJIUzI1NiJ9.eyJzdWIiOiJhIn0.c2ln")
+                .unwrap_err(),
+            JwtError::Base64DecodeError {
+                part: TokenPart::Payload,
+                error: base64::DecodeError::InvalidPadding,
+            }
+        );
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_deflated_payload() {
+        let token = deflated_token(r#"{"sub":"1234567890"}"#);
+        let payload = token.split('.').nth(1).unwrap();
+        let decoder = JwtDecoder::default();
+        assert_eq!(decoder.decode(&token).unwrap()["sub"], "1234567890");
+        assert_eq!(
+            decoder.decode_verbatim(&token).unwrap(),
+            r#"{"sub":"1234567890"}"#
+        );
+
+        // The raw bytes are inflated as well.
+        assert_eq!(
+            decoder.decode_raw(&token).unwrap(),
+            br#"{"sub":"1234567890"}"#
+        );
+
+        // Header {"alg":"HS256","zip":"GZ"}
+        let token = format!("eyJhbGciOiJIUzI1NiIsInppcCI6IkdaIn0.{}.c2ln", payload);
+        assert_eq!(
+            decoder.decode(&token).unwrap_err(),
+            JwtError::DecompressionError("Unsupported zip algorithm: \"GZ\"".to_string())
+        );
+    }
+
+    #[test]
+    fn test_decoder_max_len() {
+        let token = "eyJhbGciOiJIUzI1NiJ9.eyJzdWIiOiJhIn0.c2ln";
+        let decoder = JwtDecoder {
+            max_len: Some(10),
+            ..JwtDecoder::default()
+        };
+        assert_eq!(
+            decoder.decode(token).unwrap_err(),
+            JwtError::TokenTooLarge {
+                len: token.len(),
+                limit: 10
+            }
+        );
+        assert!(UNLIMITED_DECODER.decode(token).is_ok());
+    }
+}
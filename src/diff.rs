@@ -0,0 +1,119 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{decode_jwt_claims_strict, JwtError};
+
+// A claim present in both tokens with different values
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ClaimChange {
+    pub from: Value,
+    pub to: Value,
+}
+
+// Differences between the top-level claims of two tokens, keyed by claim name
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ClaimDiff {
+    // Claims only in the second token
+    pub added: BTreeMap<String, Value>,
+    // Claims only in the first token
+    pub removed: BTreeMap<String, Value>,
+    // Claims in both tokens whose values differ
+    pub changed: BTreeMap<String, ClaimChange>,
+}
+
+impl ClaimDiff {
+    /// Returns `true` if both tokens carry exactly the same claims.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Compares the top-level claims of two JWTs.
+///
+/// # Arguments
+/// * `token_a` - The JWT to compare from.
+/// * `token_b` - The JWT to compare to.
+///
+/// # Returns
+/// A `ClaimDiff` listing the claims added in, removed from and changed in `token_b`
+/// relative to `token_a`, or a `JwtError` if either payload cannot be decoded
+/// (`PayloadNotObject` when it is not a JSON object).
+///
+/// # Remarks
+/// Values are compared structurally, so nested objects match regardless of key order.
+/// This function does NOT verify either JWT's signature.
+pub fn diff_claims(token_a: &str, token_b: &str) -> Result<ClaimDiff, JwtError> {
+    let Value::Object(mut before) = decode_jwt_claims_strict(token_a)? else {
+        return Err(JwtError::PayloadNotObject);
+    };
+    let Value::Object(after) = decode_jwt_claims_strict(token_b)? else {
+        return Err(JwtError::PayloadNotObject);
+    };
+
+    let mut diff = ClaimDiff::default();
+    for (name, to) in after {
+        match before.remove(&name) {
+            None => {
+                diff.added.insert(name, to);
+            }
+            Some(from) if from != to => {
+                diff.changed.insert(name, ClaimChange { from, to });
+            }
+            Some(_) => {}
+        }
+    }
+    diff.removed.extend(before);
+
+    Ok(diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parts::token_with_payload;
+    use serde_json::json;
+
+    #[test]
+    fn test_added_removed_and_changed_claims() {
+        let a =
+            token_with_payload(r#"{"sub":"alice","role":"user","exp":100,"ctx":{"a":1,"b":2}}"#);
+        let b = token_with_payload(
+            r#"{"sub":"alice","role":"admin","scope":"all","ctx":{"b":2,"a":1}}"#,
+        );
+        let diff = diff_claims(&a, &b).unwrap();
+
+        assert_eq!(
+            diff.added,
+            BTreeMap::from([("scope".to_string(), json!("all"))])
+        );
+        assert_eq!(
+            diff.removed,
+            BTreeMap::from([("exp".to_string(), json!(100))])
+        );
+        assert_eq!(
+            diff.changed,
+            BTreeMap::from([(
+                "role".to_string(),
+                ClaimChange {
+                    from: json!("user"),
+                    to: json!("admin"),
+                }
+            )])
+        );
+    }
+
+    #[test]
+    fn test_identical_tokens() {
+        let a = token_with_payload(r#"{"sub":"alice"}"#);
+        assert!(diff_claims(&a, &a).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_non_object_payload() {
+        let a = token_with_payload(r#"{"sub":"alice"}"#);
+        let b = token_with_payload(r#"["sub"]"#);
+        assert_eq!(diff_claims(&a, &b).unwrap_err(), JwtError::PayloadNotObject);
+    }
+}
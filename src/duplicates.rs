@@ -0,0 +1,157 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use serde::de::{DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+
+use crate::{decode_jwt_payload_raw, JwtError};
+
+// Walks a JSON document and yields the dotted path of the first object key that appears twice
+// in the same object. `serde_json::Value` silently keeps the last occurrence instead.
+struct DuplicateKeyFinder<'a> {
+    // Path of the value being visited, empty at the top level
+    path: &'a str,
+}
+
+impl DuplicateKeyFinder<'_> {
+    // Joins the current path and an object key into a dotted path
+    fn child_path(&self, key: &str) -> String {
+        if self.path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", self.path, key)
+        }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for DuplicateKeyFinder<'_> {
+    type Value = Option<String>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for DuplicateKeyFinder<'_> {
+    type Value = Option<String>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a JSON value")
+    }
+
+    fn visit_bool<E>(self, _: bool) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_i64<E>(self, _: i64) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_u64<E>(self, _: u64) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_f64<E>(self, _: f64) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_str<E>(self, _: &str) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        // Every element has to be consumed, even after a duplicate has been found.
+        let mut found = None;
+        while let Some(nested) = seq.next_element_seed(DuplicateKeyFinder { path: self.path })? {
+            found = found.or(nested);
+        }
+        Ok(found)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut seen = HashSet::new();
+        let mut found = None;
+        while let Some(key) = map.next_key::<String>()? {
+            let path = self.child_path(&key);
+            let nested = map.next_value_seed(DuplicateKeyFinder { path: &path })?;
+            if found.is_none() {
+                found = if seen.insert(key) { nested } else { Some(path) };
+            }
+        }
+        Ok(found)
+    }
+}
+
+/// Checks the payload of a JWT for object keys that appear more than once.
+///
+/// JSON allows duplicate keys and the regular decoders keep only the last occurrence, which
+/// can hide a smuggled claim (e.g. a second `sub`) from anyone reading the decoded payload.
+///
+/// # Arguments
+/// * `token_str` - A string slice representing the JWT.
+///
+/// # Returns
+/// `Ok(())` if every key is unique, `JwtError::DuplicateClaim` naming the first repeated key
+/// (as a dotted path for nested objects), or another `JwtError` if the payload cannot be
+/// decoded.
+///
+/// # Remarks
+/// This parses the payload a second time, so it is not part of the regular decoders.
+/// This function does NOT verify the JWT's signature.
+pub fn check_duplicate_claims(token_str: &str) -> Result<(), JwtError> {
+    let payload = String::from_utf8(decode_jwt_payload_raw(token_str)?)?;
+
+    let mut deserializer = serde_json::Deserializer::from_str(&payload);
+    let duplicate = DuplicateKeyFinder { path: "" }.deserialize(&mut deserializer)?;
+    deserializer.end()?;
+
+    match duplicate {
+        Some(path) => Err(JwtError::DuplicateClaim(path)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parts::token_with_payload;
+
+    #[test]
+    fn test_duplicate_sub_is_reported() {
+        let token = token_with_payload(r#"{"sub":"alice","admin":false,"sub":"admin"}"#);
+        assert_eq!(
+            check_duplicate_claims(&token).unwrap_err(),
+            JwtError::DuplicateClaim("sub".to_string())
+        );
+    }
+
+    #[test]
+    fn test_nested_duplicate_uses_dotted_path() {
+        let token = token_with_payload(r#"{"ctx":[{"role":"user","role":"admin"}]}"#);
+        assert_eq!(
+            check_duplicate_claims(&token).unwrap_err(),
+            JwtError::DuplicateClaim("ctx.role".to_string())
+        );
+    }
+
+    #[test]
+    fn test_unique_claims_pass() {
+        let token = token_with_payload(r#"{"sub":"alice","nested":{"sub":"bob"}}"#);
+        assert!(check_duplicate_claims(&token).is_ok());
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_duplicate_in_deflated_payload() {
+        let token = crate::decoder::deflated_token(r#"{"sub":"alice","sub":"admin"}"#);
+        assert_eq!(
+            check_duplicate_claims(&token).unwrap_err(),
+            JwtError::DuplicateClaim("sub".to_string())
+        );
+        let token = crate::decoder::deflated_token(r#"{"sub":"alice"}"#);
+        assert!(check_duplicate_claims(&token).is_ok());
+    }
+}
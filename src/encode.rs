@@ -0,0 +1,51 @@
+use serde_json::Value;
+
+use crate::{b64url_encode, JwtError};
+
+/// Builds an unsigned JWT from a header and payload, e.g. to generate test fixtures.
+///
+/// # Arguments
+/// * `header` - The header, such as `{"alg": "none"}`.
+/// * `payload` - The claims.
+///
+/// # Returns
+/// The compact serialization `header.payload.` with an empty signature part, or a
+/// `JwtError` if either value cannot be serialized.
+///
+/// # Remarks
+/// Both values are serialized as compact JSON and encoded as unpadded Base64url. The header
+/// is used as given; set `alg` to `"none"` if the token should declare that it is unsigned.
+pub fn encode_unsigned(header: &Value, payload: &Value) -> Result<String, JwtError> {
+    let header_json = serde_json::to_string(header)?;
+    let payload_json = serde_json::to_string(payload)?;
+    Ok(format!(
+        "{}.{}.",
+        b64url_encode(header_json.as_bytes()),
+        b64url_encode(payload_json.as_bytes())
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode_jwt_claims, decode_jwt_header_value, split_token};
+    use serde_json::json;
+
+    #[test]
+    fn test_encode_then_decode_round_trip() {
+        let header = json!({"alg": "none", "typ": "JWT"});
+        let payload = json!({
+            "sub": "1234567890",
+            "name": "John Doe",
+            "iat": 1516239022,
+            "admin": true,
+            "email": "john.doe@example.com"
+        });
+        let token = encode_unsigned(&header, &payload).unwrap();
+
+        assert!(token.ends_with('.'));
+        assert_eq!(split_token(&token).unwrap().2, Some(""));
+        assert_eq!(decode_jwt_header_value(&token).unwrap(), header);
+        assert_eq!(decode_jwt_claims(&token).unwrap(), payload);
+    }
+}
@@ -0,0 +1,293 @@
+use alloc::string::{FromUtf8Error, String, ToString};
+use core::fmt;
+
+use crate::TokenPart;
+
+// Define a custom error type for better error handling
+#[derive(Debug)]
+pub enum JwtError {
+    InvalidTokenFormat(String),
+    Base64DecodeError {
+        part: TokenPart,
+        error: base64::DecodeError,
+    },
+    InvalidBase64(base64::DecodeError),
+    JsonParseError(serde_json::Error),
+    Utf8Error(FromUtf8Error),
+    MissingClaim(String),
+    InvalidClaimType(String),
+    SignatureMismatch,
+    UnsupportedAlgorithm(String),
+    AlgorithmKeyMismatch {
+        alg: String,
+        key: &'static str,
+    },
+    KeyParseError(String),
+    NoneAlgorithmRejected,
+    #[cfg(feature = "std")]
+    YamlSerializeError(serde_yaml::Error),
+    PayloadNotObject,
+    EncryptedTokenUnsupported,
+    TokenTooLarge {
+        len: usize,
+        limit: usize,
+    },
+    DuplicateClaim(String),
+    KeyNotFound(String),
+    JwksFetchError(String),
+    UnexpectedTokenType(String),
+    TokenNotFoundInUrl(String),
+    DecompressionError(String),
+    PathNotFound(String),
+    MissingSignature,
+    NestingTooDeep(usize),
+    UnexpectedClaim(String),
+    #[cfg(feature = "std")]
+    IoError(std::io::Error),
+}
+
+impl JwtError {
+    /// Returns a stable identifier for the error variant, e.g. `"Base64DecodeError"`, for
+    /// tools that branch on the kind of failure instead of parsing the message.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            JwtError::InvalidTokenFormat(_) => "InvalidTokenFormat",
+            JwtError::Base64DecodeError { .. } => "Base64DecodeError",
+            JwtError::InvalidBase64(_) => "InvalidBase64",
+            JwtError::JsonParseError(_) => "JsonParseError",
+            JwtError::Utf8Error(_) => "Utf8Error",
+            JwtError::MissingClaim(_) => "MissingClaim",
+            JwtError::InvalidClaimType(_) => "InvalidClaimType",
+            JwtError::SignatureMismatch => "SignatureMismatch",
+            JwtError::UnsupportedAlgorithm(_) => "UnsupportedAlgorithm",
+            JwtError::AlgorithmKeyMismatch { .. } => "AlgorithmKeyMismatch",
+            JwtError::KeyParseError(_) => "KeyParseError",
+            JwtError::NoneAlgorithmRejected => "NoneAlgorithmRejected",
+            #[cfg(feature = "std")]
+            JwtError::YamlSerializeError(_) => "YamlSerializeError",
+            JwtError::PayloadNotObject => "PayloadNotObject",
+            JwtError::EncryptedTokenUnsupported => "EncryptedTokenUnsupported",
+            JwtError::TokenTooLarge { .. } => "TokenTooLarge",
+            JwtError::DuplicateClaim(_) => "DuplicateClaim",
+            JwtError::KeyNotFound(_) => "KeyNotFound",
+            JwtError::JwksFetchError(_) => "JwksFetchError",
+            JwtError::UnexpectedTokenType(_) => "UnexpectedTokenType",
+            JwtError::TokenNotFoundInUrl(_) => "TokenNotFoundInUrl",
+            JwtError::DecompressionError(_) => "DecompressionError",
+            JwtError::PathNotFound(_) => "PathNotFound",
+            JwtError::MissingSignature => "MissingSignature",
+            JwtError::NestingTooDeep(_) => "NestingTooDeep",
+            JwtError::UnexpectedClaim(_) => "UnexpectedClaim",
+            #[cfg(feature = "std")]
+            JwtError::IoError(_) => "IoError",
+        }
+    }
+}
+
+// Implement Display trait for JwtError to allow easy printing
+impl fmt::Display for JwtError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JwtError::InvalidTokenFormat(msg) => write!(f, "Invalid JWT format: {}", msg),
+            JwtError::Base64DecodeError { part, error } => {
+                write!(f, "Base64 decoding error in {}: {}", part, error)
+            }
+            JwtError::InvalidBase64(e) => write!(f, "Base64 decoding error: {}", e),
+            JwtError::JsonParseError(e) => write!(f, "JSON parsing error: {}", e),
+            // The offset is into the decoded bytes, where the valid UTF-8 prefix ends.
+            JwtError::Utf8Error(e) => {
+                let offset = e.utf8_error().valid_up_to();
+                match e.utf8_error().error_len() {
+                    Some(len) => write!(
+                        f,
+                        "UTF-8 conversion error at byte offset {}: invalid sequence of {} bytes",
+                        offset, len
+                    ),
+                    None => write!(
+                        f,
+                        "UTF-8 conversion error at byte offset {}: incomplete sequence at the end",
+                        offset
+                    ),
+                }
+            }
+            JwtError::MissingClaim(name) => write!(f, "Missing claim: {}", name),
+            JwtError::InvalidClaimType(name) => write!(f, "Invalid type for claim: {}", name),
+            JwtError::SignatureMismatch => write!(f, "Signature verification failed"),
+            JwtError::UnsupportedAlgorithm(alg) => write!(f, "Unsupported algorithm: {}", alg),
+            JwtError::AlgorithmKeyMismatch { alg, key } => {
+                write!(f, "Algorithm {} cannot be verified with {}", alg, key)
+            }
+            JwtError::KeyParseError(msg) => write!(f, "Key parsing error: {}", msg),
+            JwtError::NoneAlgorithmRejected => {
+                write!(f, "The 'none' algorithm is not accepted for verification")
+            }
+            #[cfg(feature = "std")]
+            JwtError::YamlSerializeError(e) => write!(f, "YAML serialization error: {}", e),
+            JwtError::PayloadNotObject => write!(f, "Payload is not a JSON object"),
+            JwtError::EncryptedTokenUnsupported => write!(
+                f,
+                "Token has five parts, so it is an encrypted JWE (parts 4 and 5 are the \
+                 ciphertext and authentication tag); JWE decryption is not supported"
+            ),
+            JwtError::TokenTooLarge { len, limit } => write!(
+                f,
+                "Token is {} bytes long, which exceeds the limit of {} bytes",
+                len, limit
+            ),
+            JwtError::DuplicateClaim(name) => write!(f, "Duplicate claim: {}", name),
+            JwtError::KeyNotFound(kid) => write!(f, "No key in the key set has kid: {}", kid),
+            JwtError::JwksFetchError(msg) => write!(f, "Failed to fetch JWKS: {}", msg),
+            JwtError::UnexpectedTokenType(typ) => write!(f, "Unexpected token type: {}", typ),
+            JwtError::TokenNotFoundInUrl(param) => {
+                write!(f, "URL has no {} parameter holding a token", param)
+            }
+            JwtError::DecompressionError(msg) => write!(f, "Decompression error: {}", msg),
+            JwtError::PathNotFound(path) => write!(f, "No value at claim path: {}", path),
+            JwtError::MissingSignature => write!(f, "Token has no signature"),
+            JwtError::NestingTooDeep(limit) => {
+                write!(f, "Nested JWTs go deeper than the limit of {}", limit)
+            }
+            JwtError::UnexpectedClaim(name) => write!(f, "Unexpected claim: {}", name),
+            #[cfg(feature = "std")]
+            JwtError::IoError(e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+// Variants wrapping library errors compare by their message, since not all of those
+// errors implement `PartialEq`
+impl PartialEq for JwtError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (JwtError::InvalidTokenFormat(a), JwtError::InvalidTokenFormat(b)) => a == b,
+            (
+                JwtError::Base64DecodeError { part, error },
+                JwtError::Base64DecodeError {
+                    part: other_part,
+                    error: other_error,
+                },
+            ) => part == other_part && error == other_error,
+            (JwtError::InvalidBase64(a), JwtError::InvalidBase64(b)) => a == b,
+            (JwtError::JsonParseError(a), JwtError::JsonParseError(b)) => {
+                a.to_string() == b.to_string()
+            }
+            (JwtError::Utf8Error(a), JwtError::Utf8Error(b)) => a == b,
+            (JwtError::MissingClaim(a), JwtError::MissingClaim(b)) => a == b,
+            (JwtError::InvalidClaimType(a), JwtError::InvalidClaimType(b)) => a == b,
+            (JwtError::SignatureMismatch, JwtError::SignatureMismatch) => true,
+            (JwtError::UnsupportedAlgorithm(a), JwtError::UnsupportedAlgorithm(b)) => a == b,
+            (
+                JwtError::AlgorithmKeyMismatch { alg, key },
+                JwtError::AlgorithmKeyMismatch {
+                    alg: other_alg,
+                    key: other_key,
+                },
+            ) => alg == other_alg && key == other_key,
+            (JwtError::KeyParseError(a), JwtError::KeyParseError(b)) => a == b,
+            (JwtError::NoneAlgorithmRejected, JwtError::NoneAlgorithmRejected) => true,
+            #[cfg(feature = "std")]
+            (JwtError::YamlSerializeError(a), JwtError::YamlSerializeError(b)) => {
+                a.to_string() == b.to_string()
+            }
+            (JwtError::PayloadNotObject, JwtError::PayloadNotObject) => true,
+            (JwtError::EncryptedTokenUnsupported, JwtError::EncryptedTokenUnsupported) => true,
+            (
+                JwtError::TokenTooLarge { len, limit },
+                JwtError::TokenTooLarge {
+                    len: other_len,
+                    limit: other_limit,
+                },
+            ) => len == other_len && limit == other_limit,
+            (JwtError::DuplicateClaim(a), JwtError::DuplicateClaim(b)) => a == b,
+            (JwtError::KeyNotFound(a), JwtError::KeyNotFound(b)) => a == b,
+            (JwtError::JwksFetchError(a), JwtError::JwksFetchError(b)) => a == b,
+            (JwtError::UnexpectedTokenType(a), JwtError::UnexpectedTokenType(b)) => a == b,
+            (JwtError::TokenNotFoundInUrl(a), JwtError::TokenNotFoundInUrl(b)) => a == b,
+            (JwtError::DecompressionError(a), JwtError::DecompressionError(b)) => a == b,
+            (JwtError::PathNotFound(a), JwtError::PathNotFound(b)) => a == b,
+            (JwtError::MissingSignature, JwtError::MissingSignature) => true,
+            (JwtError::NestingTooDeep(a), JwtError::NestingTooDeep(b)) => a == b,
+            (JwtError::UnexpectedClaim(a), JwtError::UnexpectedClaim(b)) => a == b,
+            #[cfg(feature = "std")]
+            (JwtError::IoError(a), JwtError::IoError(b)) => {
+                a.kind() == b.kind() && a.to_string() == b.to_string()
+            }
+            _ => false,
+        }
+    }
+}
+
+// Implement Error trait for JwtError
+#[cfg(feature = "std")]
+impl std::error::Error for JwtError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            JwtError::Base64DecodeError { error, .. } => Some(error),
+            JwtError::InvalidBase64(e) => Some(e),
+            JwtError::JsonParseError(e) => Some(e),
+            JwtError::Utf8Error(e) => Some(e),
+            JwtError::YamlSerializeError(e) => Some(e),
+            JwtError::IoError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+// Conversion from underlying errors to JwtError
+impl From<serde_json::Error> for JwtError {
+    fn from(err: serde_json::Error) -> JwtError {
+        JwtError::JsonParseError(err)
+    }
+}
+
+impl From<FromUtf8Error> for JwtError {
+    fn from(err: FromUtf8Error) -> JwtError {
+        JwtError::Utf8Error(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<serde_yaml::Error> for JwtError {
+    fn from(err: serde_yaml::Error) -> JwtError {
+        JwtError::YamlSerializeError(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for JwtError {
+    fn from(err: std::io::Error) -> JwtError {
+        JwtError::IoError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_errors_compare_by_variant_and_contents() {
+        assert_eq!(
+            JwtError::MissingClaim("exp".to_string()),
+            JwtError::MissingClaim("exp".to_string())
+        );
+        assert_ne!(
+            JwtError::MissingClaim("exp".to_string()),
+            JwtError::MissingClaim("nbf".to_string())
+        );
+        assert_ne!(
+            JwtError::MissingClaim("exp".to_string()),
+            JwtError::InvalidClaimType("exp".to_string())
+        );
+        assert_eq!(JwtError::PayloadNotObject, JwtError::PayloadNotObject);
+    }
+
+    #[test]
+    fn test_wrapped_errors_compare_by_message() {
+        let parse = || JwtError::from(serde_json::from_str::<serde_json::Value>("{").unwrap_err());
+        assert_eq!(parse(), parse());
+        assert_ne!(
+            parse(),
+            JwtError::from(serde_json::from_str::<serde_json::Value>("[1,").unwrap_err())
+        );
+    }
+}
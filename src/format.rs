@@ -0,0 +1,368 @@
+use serde::Serialize;
+use serde_json::ser::PrettyFormatter;
+use serde_json::Value;
+
+use crate::validation::past_expiry;
+use crate::JwtError;
+
+// How decoded JSON is laid out when serialized
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JsonStyle {
+    // Indented, multi-line output for humans
+    #[default]
+    Pretty,
+    // Single-line output, one object per line for tools like `jq`
+    Compact,
+    // Multi-line output indented by the given number of spaces instead of two
+    Indented(usize),
+}
+
+// The serialization format for decoded values
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    // JSON, laid out according to a `JsonStyle`
+    #[default]
+    Json,
+    // YAML, for embedding decoded claims in YAML documents
+    Yaml,
+    // Aligned `key = value` rows with nested objects flattened into dotted paths
+    Table,
+}
+
+/// Serializes a JSON value using the requested `JsonStyle`.
+///
+/// # Arguments
+/// * `value` - The JSON value to serialize.
+/// * `style` - Whether to pretty-print or emit compact single-line JSON.
+///
+/// # Returns
+/// A `Result` containing the serialized JSON string or a `JwtError`.
+pub fn to_json_string(value: &Value, style: JsonStyle) -> Result<String, JwtError> {
+    let json = match style {
+        JsonStyle::Pretty => serde_json::to_string_pretty(value)?,
+        JsonStyle::Compact => serde_json::to_string(value)?,
+        JsonStyle::Indented(width) => to_indented_string(value, width)?,
+    };
+    Ok(json)
+}
+
+// Pretty-prints `value` like `to_string_pretty`, but with `width` spaces per level.
+fn to_indented_string(value: &Value, width: usize) -> Result<String, JwtError> {
+    let indent = vec![b' '; width];
+    let mut json = Vec::new();
+    let mut serializer =
+        serde_json::Serializer::with_formatter(&mut json, PrettyFormatter::with_indent(&indent));
+    value.serialize(&mut serializer)?;
+    Ok(String::from_utf8(json)?)
+}
+
+/// Serializes a decoded value in the requested `OutputFormat`.
+///
+/// # Arguments
+/// * `value` - The decoded value to serialize.
+/// * `format` - The output format to use.
+/// * `style` - The JSON layout, used when `format` is `OutputFormat::Json`.
+///
+/// # Returns
+/// A `Result` containing the serialized text (without a trailing newline) or a `JwtError`.
+pub fn render_value(
+    value: &Value,
+    format: OutputFormat,
+    style: JsonStyle,
+) -> Result<String, JwtError> {
+    match format {
+        OutputFormat::Json => to_json_string(value, style),
+        OutputFormat::Yaml => {
+            let yaml = serde_yaml::to_string(value)?;
+            Ok(yaml.trim_end().to_string())
+        }
+        OutputFormat::Table => Ok(render_table(value)),
+    }
+}
+
+// Renders a leaf value for the table format: strings bare, everything else as JSON.
+fn table_cell(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Flattens a JSON value into `(path, value)` rows for the table format.
+///
+/// # Arguments
+/// * `prefix` - The dotted path of `value` (empty for the top level).
+/// * `value` - The value to flatten.
+/// * `out` - Receives one row per leaf value.
+///
+/// # Remarks
+/// Nested objects extend the path with `.key`; arrays are joined into one comma-separated row.
+pub fn flatten_value(prefix: &str, value: &Value, out: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten_value(&path, child, out);
+            }
+        }
+        Value::Array(items) => {
+            let joined: Vec<String> = items.iter().map(table_cell).collect();
+            out.push((prefix.to_string(), joined.join(", ")));
+        }
+        leaf => out.push((prefix.to_string(), table_cell(leaf))),
+    }
+}
+
+// Renders flattened rows with the keys padded to the widest key.
+fn render_table(value: &Value) -> String {
+    let mut rows = Vec::new();
+    flatten_value("", value, &mut rows);
+
+    let width = rows.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+    rows.iter()
+        .map(|(key, cell)| format!("{:<width$} = {}", key, cell, width = width))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// ANSI escape sequences used by `colorize_json`
+const COLOR_KEY: &str = "\x1b[34m";
+const COLOR_STRING: &str = "\x1b[32m";
+const COLOR_SCALAR: &str = "\x1b[33m";
+const COLOR_RESET: &str = "\x1b[0m";
+
+// Writes `value` as pretty JSON with ANSI colors, matching `to_string_pretty`'s layout.
+fn write_colored(value: &Value, indent: usize, out: &mut String) {
+    const INDENT: &str = "  ";
+
+    match value {
+        Value::Object(map) if map.is_empty() => out.push_str("{}"),
+        Value::Object(map) => {
+            out.push_str("{\n");
+            for (i, (key, child)) in map.iter().enumerate() {
+                out.push_str(&INDENT.repeat(indent + 1));
+                out.push_str(COLOR_KEY);
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push_str(COLOR_RESET);
+                out.push_str(": ");
+                write_colored(child, indent + 1, out);
+                out.push_str(if i + 1 < map.len() { ",\n" } else { "\n" });
+            }
+            out.push_str(&INDENT.repeat(indent));
+            out.push('}');
+        }
+        Value::Array(items) if items.is_empty() => out.push_str("[]"),
+        Value::Array(items) => {
+            out.push_str("[\n");
+            for (i, item) in items.iter().enumerate() {
+                out.push_str(&INDENT.repeat(indent + 1));
+                write_colored(item, indent + 1, out);
+                out.push_str(if i + 1 < items.len() { ",\n" } else { "\n" });
+            }
+            out.push_str(&INDENT.repeat(indent));
+            out.push(']');
+        }
+        Value::String(_) => {
+            out.push_str(COLOR_STRING);
+            out.push_str(&value.to_string());
+            out.push_str(COLOR_RESET);
+        }
+        scalar => {
+            out.push_str(COLOR_SCALAR);
+            out.push_str(&scalar.to_string());
+            out.push_str(COLOR_RESET);
+        }
+    }
+}
+
+/// Pretty-prints a JSON value with ANSI colors for terminal display.
+///
+/// Keys, strings and other scalars (numbers, booleans, null) each get their own color; the
+/// layout is otherwise identical to `JsonStyle::Pretty`.
+pub fn colorize_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_colored(value, 0, &mut out);
+    out
+}
+
+// Removes ANSI escape sequences, so colorized lines can be matched like plain ones.
+fn strip_ansi(line: &str) -> String {
+    let mut plain = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|c| *c == 'm');
+        } else {
+            plain.push(c);
+        }
+    }
+    plain
+}
+
+// Renders a span of seconds in its largest whole unit, e.g. `3d` or `42s`.
+fn relative_span(secs: u64) -> String {
+    match secs {
+        86_400.. => format!("{}d", secs / 86_400),
+        3_600.. => format!("{}h", secs / 3_600),
+        60.. => format!("{}m", secs / 60),
+        _ => format!("{}s", secs),
+    }
+}
+
+// Describes an `exp`, `nbf` or `iat` line relative to `now`, or `None` for any other line.
+fn time_annotation(line: &str, now: i64) -> Option<String> {
+    let plain = strip_ansi(line);
+    let (key, value) = plain.trim().split_once(": ")?;
+    let secs: i64 = value.trim_end_matches(',').parse().ok()?;
+    let delta = secs.saturating_sub(now);
+    let span = relative_span(delta.unsigned_abs());
+
+    let note = match key {
+        // The same boundary as --check-time, so a token is not EXPIRED while it still passes.
+        "\"exp\"" if past_expiry(secs, now, 0) => format!("EXPIRED {} ago", span),
+        "\"exp\"" => format!("expires in {}", span),
+        "\"nbf\"" if delta > 0 => format!("NOT YET VALID, starts in {}", span),
+        "\"nbf\"" => format!("valid since {} ago", span),
+        "\"iat\"" if delta > 0 => format!("issued {} in the future", span),
+        "\"iat\"" => format!("issued {} ago", span),
+        _ => return None,
+    };
+    Some(note)
+}
+
+/// Appends a comment describing how each `exp`, `nbf` and `iat` line of pretty-printed JSON
+/// relates to `now`, e.g. `"exp": 1701502400,  // EXPIRED 3d ago`.
+///
+/// # Arguments
+/// * `json` - Pretty-printed JSON, optionally colorized by `colorize_json`.
+/// * `now` - The current time in seconds since the Unix epoch.
+///
+/// # Returns
+/// The annotated text. It is meant for human reading only and is NOT valid JSON.
+///
+/// # Remarks
+/// Claims are matched at any depth, so the payload inside `decode_jwt_all` output is
+/// annotated too. Values that are not integers are left without a comment.
+pub fn annotate_time_claims(json: &str, now: i64) -> String {
+    json.lines()
+        .map(|line| match time_annotation(line, now) {
+            Some(note) => format!("{}  // {}", line, note),
+            None => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{encode_unsigned, is_expired, FixedClock};
+    use serde_json::json;
+    use std::time::Duration;
+
+    #[test]
+    fn test_yaml_round_trips_nested_values() {
+        let value = json!({
+            "sub": "1234567890",
+            "address": {"city": "NYC", "zip": "10001"},
+            "roles": ["admin", "user"],
+            "groups": [{"id": 1}, {"id": 2}],
+        });
+        let yaml = render_value(&value, OutputFormat::Yaml, JsonStyle::Pretty).unwrap();
+        assert!(yaml.contains("city: NYC"));
+        let parsed: Value = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed, value);
+    }
+
+    #[test]
+    fn test_indented_style_uses_requested_width() {
+        let value = json!({"sub": "1234567890", "roles": ["admin"]});
+        assert_eq!(
+            to_json_string(&value, JsonStyle::Indented(4)).unwrap(),
+            "{\n    \"sub\": \"1234567890\",\n    \"roles\": [\n        \"admin\"\n    ]\n}"
+        );
+        assert_eq!(
+            to_json_string(&value, JsonStyle::Indented(2)).unwrap(),
+            to_json_string(&value, JsonStyle::Pretty).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_flatten_value_uses_dotted_paths() {
+        let value = json!({"address": {"city": "NYC"}, "roles": ["admin", "user"], "age": 30});
+        let mut rows = Vec::new();
+        flatten_value("", &value, &mut rows);
+        assert!(rows.contains(&("address.city".to_string(), "NYC".to_string())));
+        assert!(rows.contains(&("roles".to_string(), "admin, user".to_string())));
+        assert!(rows.contains(&("age".to_string(), "30".to_string())));
+    }
+
+    #[test]
+    fn test_table_aligns_keys() {
+        let value = json!({"sub": "1234567890", "address": {"city": "NYC"}});
+        let table = render_value(&value, OutputFormat::Table, JsonStyle::Pretty).unwrap();
+        assert_eq!(table, "sub          = 1234567890\naddress.city = NYC");
+    }
+
+    #[test]
+    fn test_exp_annotation_matches_expiry_check() {
+        let clock = FixedClock(1_700_000_000);
+        for exp in [clock.0 - 1, clock.0, clock.0 + 1] {
+            let claims = json!({ "exp": exp });
+            let token = encode_unsigned(&json!({"alg": "none"}), &claims).unwrap();
+            let annotated = annotate_time_claims(
+                &to_json_string(&claims, JsonStyle::Pretty).unwrap(),
+                clock.0,
+            );
+            assert_eq!(
+                annotated.contains("EXPIRED"),
+                is_expired(&token, Duration::ZERO, &clock).unwrap(),
+                "{}",
+                annotated
+            );
+        }
+        // At exp itself the token is still valid.
+        assert!(annotate_time_claims("  \"exp\": 1700000000", clock.0).ends_with("expires in 0s"));
+    }
+
+    #[test]
+    fn test_annotate_time_claims() {
+        let now = 1_700_000_000;
+        let value = json!({
+            "sub": "1234567890",
+            "iat": now - 3 * 86_400,
+            "nbf": now + 300,
+            "exp": now - 3 * 86_400 - 60,
+        });
+        let pretty = to_json_string(&value, JsonStyle::Pretty).unwrap();
+        assert_eq!(
+            annotate_time_claims(&pretty, now),
+            "{\n  \"sub\": \"1234567890\",\n  \"iat\": 1699740800,  // issued 3d ago\n  \
+             \"nbf\": 1700000300,  // NOT YET VALID, starts in 5m\n  \
+             \"exp\": 1699740740  // EXPIRED 3d ago\n}"
+        );
+
+        let colored = annotate_time_claims(&colorize_json(&json!({"exp": now + 7_200})), now);
+        assert!(colored.ends_with("\x1b[0m  // expires in 2h\n}"));
+    }
+
+    #[test]
+    fn test_colorized_json_matches_pretty_layout() {
+        let value =
+            json!({"sub": "1234567890", "admin": true, "roles": ["a", 1, null], "empty": {}});
+        let colored = colorize_json(&value);
+        assert!(colored.contains("\x1b[34m\"sub\"\x1b[0m: \x1b[32m\"1234567890\"\x1b[0m"));
+        assert!(colored.contains("\x1b[33mtrue\x1b[0m"));
+
+        let stripped = colored
+            .replace(COLOR_KEY, "")
+            .replace(COLOR_STRING, "")
+            .replace(COLOR_SCALAR, "")
+            .replace(COLOR_RESET, "");
+        assert_eq!(stripped, serde_json::to_string_pretty(&value).unwrap());
+    }
+}
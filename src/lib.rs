@@ -0,0 +1,124 @@
+//! Decodes the header and payload of JSON Web Tokens.
+//!
+//! Decoding never verifies signatures; use the `verify_*` functions for that.
+//!
+//! The crate backs the `jwt_reader` command-line tool, but the decoding functions can be
+//! used directly:
+//!
+//! ```
+//! # #[cfg(feature = "std")] {
+//! let token = "eyJhbGciOiJIUzI1NiJ9.eyJzdWIiOiIxMjM0NTY3ODkwIn0.c2ln";
+//! let payload = jwt_reader::decode_jwt_payload(token).unwrap();
+//! assert!(payload.contains("\"sub\": \"1234567890\""));
+//! # }
+//! ```
+//!
+//! Everything beyond splitting tokens and Base64-decoding their parts needs the default
+//! `std` feature. Without it the crate is `no_std` and only requires `alloc`, so those two
+//! steps can run on embedded targets:
+//!
+//! ```
+//! let token = "eyJhbGciOiJIUzI1NiJ9.eyJzdWIiOiIxMjM0NTY3ODkwIn0.c2ln";
+//! let (_, payload, _) = jwt_reader::split_token(token).unwrap();
+//! let bytes = jwt_reader::Base64Policy::default().decode(payload).unwrap();
+//! assert_eq!(bytes, br#"{"sub":"1234567890"}"#);
+//! ```
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+#[macro_use]
+mod logging;
+
+#[cfg(feature = "std")]
+mod claims;
+#[cfg(feature = "std")]
+mod clock;
+#[cfg(feature = "std")]
+mod decode;
+#[cfg(feature = "std")]
+mod decoder;
+#[cfg(feature = "std")]
+mod diff;
+#[cfg(feature = "std")]
+mod duplicates;
+#[cfg(feature = "std")]
+mod encode;
+mod error;
+#[cfg(feature = "std")]
+mod format;
+#[cfg(feature = "std")]
+mod jwks;
+#[cfg(feature = "std")]
+mod jwt;
+mod parts;
+#[cfg(feature = "std")]
+mod path;
+#[cfg(feature = "std")]
+mod transform;
+#[cfg(feature = "std")]
+mod url;
+#[cfg(feature = "std")]
+mod validation;
+#[cfg(feature = "std")]
+mod verify;
+
+#[cfg(feature = "std")]
+pub use claims::{decode_standard_claims, Audience, StandardClaims};
+#[cfg(feature = "std")]
+pub use clock::{Clock, FixedClock, SystemClock};
+#[cfg(feature = "std")]
+pub use decode::{
+    algorithm, claim_names, decode_base64_json, decode_jwt_all, decode_jwt_claims,
+    decode_jwt_claims_strict, decode_jwt_claims_timed, decode_jwt_header, decode_jwt_header_value,
+    decode_jwt_payload, decode_jwt_payload_pretty_with_indent, decode_jwt_payload_raw,
+    decode_jwt_payload_verbatim, decode_jwt_payload_with_style, decode_nested_claims,
+    decode_reader, decode_signature_bytes, decode_to_writer, get_claim, read_token,
+    strip_signature, token_type, DEFAULT_MAX_NESTING_DEPTH,
+};
+#[cfg(feature = "std")]
+pub use decoder::{DecodeTimings, JwtDecoder};
+#[cfg(feature = "std")]
+pub use diff::{diff_claims, ClaimChange, ClaimDiff};
+#[cfg(feature = "std")]
+pub use duplicates::check_duplicate_claims;
+#[cfg(feature = "std")]
+pub use encode::encode_unsigned;
+pub use error::JwtError;
+#[cfg(feature = "std")]
+pub use format::{
+    annotate_time_claims, colorize_json, flatten_value, render_value, to_json_string, JsonStyle,
+    OutputFormat,
+};
+#[cfg(feature = "jwks")]
+pub use jwks::fetch_jwks;
+#[cfg(feature = "std")]
+pub use jwks::{key_for_token, select_key, Jwk, Jwks};
+#[cfg(feature = "std")]
+pub use jwt::Jwt;
+pub use parts::{
+    b64url_decode, b64url_encode, check_token_len, has_signature, remove_whitespace, split_token,
+    trim_stray_dots, Base64Policy, TokenPart, DEFAULT_MAX_TOKEN_LEN,
+};
+#[cfg(feature = "std")]
+pub use path::{get_claim_path, query_path};
+#[cfg(feature = "std")]
+pub use transform::{
+    expand_json_strings, humanize_timestamps, humanize_timestamps_in, redact_claims,
+    redact_strings, select_claims, TimestampZone,
+};
+#[cfg(feature = "std")]
+pub use url::extract_token_from_url;
+#[cfg(feature = "std")]
+pub use validation::{
+    check_allowed_claims, check_audience, check_issuer, is_expired, is_not_yet_valid,
+    require_token_type, seconds_until_expiry, summarize, token_age_seconds, validate,
+    validate_time_claims, TokenSummary, ValidationOptions, ValidationReport,
+};
+#[cfg(feature = "std")]
+pub use verify::{
+    verify, verify_any, verify_detached, verify_es256, verify_hmac, verify_hs256, verify_rs256,
+    Algorithm, VerificationKey, VerifyOutcome,
+};
@@ -0,0 +1,36 @@
+// Logging macros that forward to the `log` crate when the `logging` feature is enabled.
+// Without it they expand to dead code, so the arguments are still type-checked (and count as
+// used) but nothing is formatted at runtime.
+
+macro_rules! trace {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "logging")]
+        log::trace!($($arg)*);
+        #[cfg(not(feature = "logging"))]
+        if false {
+            let _ = format_args!($($arg)*);
+        }
+    }};
+}
+
+macro_rules! debug {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "logging")]
+        log::debug!($($arg)*);
+        #[cfg(not(feature = "logging"))]
+        if false {
+            let _ = format_args!($($arg)*);
+        }
+    }};
+}
+
+macro_rules! error {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "logging")]
+        log::error!($($arg)*);
+        #[cfg(not(feature = "logging"))]
+        if false {
+            let _ = format_args!($($arg)*);
+        }
+    }};
+}
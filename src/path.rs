@@ -0,0 +1,98 @@
+use serde_json::Value;
+
+use crate::{decode_jwt_claims, JwtError};
+
+/// Navigates a decoded value along a dotted path with bracketed array indices, such as
+/// `address.city` or `groups[0].name`.
+///
+/// # Arguments
+/// * `value` - The value to navigate, e.g. the decoded claims.
+/// * `path` - The path to follow. Each dot-separated segment names an object key and may be
+///   followed by one or more `[n]` array indices; the first segment may be indices alone.
+///
+/// # Returns
+/// The addressed sub-value, or `JwtError::PathNotFound` when the path is malformed or a key
+/// or index along it does not exist.
+///
+/// # Remarks
+/// Keys that themselves contain `.` or `[`, like namespaced `https://example.com/roles`
+/// claims, cannot be addressed; read those with `get_claim` instead.
+pub fn query_path<'a>(value: &'a Value, path: &str) -> Result<&'a Value, JwtError> {
+    let not_found = || JwtError::PathNotFound(path.to_string());
+
+    let mut current = value;
+    for (position, segment) in path.split('.').enumerate() {
+        let (key, mut indices) = segment.split_at(segment.find('[').unwrap_or(segment.len()));
+        if key.is_empty() && (indices.is_empty() || position > 0) {
+            return Err(not_found());
+        }
+        if !key.is_empty() {
+            current = current.get(key).ok_or_else(not_found)?;
+        }
+        while !indices.is_empty() {
+            let (index, rest) = indices
+                .strip_prefix('[')
+                .and_then(|indices| indices.split_once(']'))
+                .ok_or_else(not_found)?;
+            let index: usize = index.parse().map_err(|_| not_found())?;
+            current = current.get(index).ok_or_else(not_found)?;
+            indices = rest;
+        }
+    }
+    Ok(current)
+}
+
+/// Looks up a possibly nested claim in the payload of a JWT by path, as `query_path` does.
+///
+/// # Arguments
+/// * `token_str` - A string slice representing the JWT.
+/// * `path` - The path of the claim, such as `address.city`.
+///
+/// # Returns
+/// A `Result` containing the addressed value or a `JwtError` (`PathNotFound` when the path
+/// does not resolve).
+///
+/// # Remarks
+/// This function does NOT verify the JWT's signature.
+pub fn get_claim_path(token_str: &str, path: &str) -> Result<Value, JwtError> {
+    let claims = decode_jwt_claims(token_str)?;
+    query_path(&claims, path).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_query_path() {
+        let value = json!({
+            "address": {"city": "NYC"},
+            "groups": [{"name": "admins", "ids": [7, 8]}],
+        });
+        assert_eq!(query_path(&value, "address.city").unwrap(), "NYC");
+        assert_eq!(query_path(&value, "groups[0].name").unwrap(), "admins");
+        assert_eq!(query_path(&value, "groups[0].ids[1]").unwrap(), 8);
+        assert_eq!(query_path(&value["groups"], "[0].ids[0]").unwrap(), 7);
+    }
+
+    #[test]
+    fn test_unresolved_paths() {
+        let value = json!({"address": {"city": "NYC"}, "groups": ["admins"]});
+        for path in [
+            "address.zip",
+            "groups[1]",
+            "groups.name",
+            "address[0]",
+            "groups[x]",
+            "groups[0",
+            "address..city",
+            "",
+        ] {
+            assert_eq!(
+                query_path(&value, path).unwrap_err(),
+                JwtError::PathNotFound(path.to_string())
+            );
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use chrono::{DateTime, Local, SecondsFormat};
+use serde_json::Value;
+
+// Registered claims that hold NumericDate timestamps
+const TIMESTAMP_CLAIMS: [&str; 4] = ["iat", "nbf", "exp", "auth_time"];
+
+// The timezone humanized timestamps are rendered in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TimestampZone {
+    #[default]
+    Utc,
+    // The system's local timezone
+    Local,
+}
+
+/// Rewrites the well-known timestamp claims (`iat`, `nbf`, `exp`, `auth_time`) into RFC 3339
+/// UTC strings so they can be read without manual conversion.
+///
+/// # Arguments
+/// * `claims` - The decoded payload, modified in place.
+///
+/// # Remarks
+/// Only positive integer values are rewritten; anything else is left untouched.
+pub fn humanize_timestamps(claims: &mut Value) {
+    humanize_timestamps_in(claims, TimestampZone::Utc);
+}
+
+/// Rewrites the well-known timestamp claims like `humanize_timestamps`, in the given timezone.
+///
+/// # Arguments
+/// * `claims` - The decoded payload, modified in place.
+/// * `zone` - The timezone to render in. The offset is always written out, e.g. `+00:00`
+///   for UTC, so the strings are unambiguous either way.
+pub fn humanize_timestamps_in(claims: &mut Value, zone: TimestampZone) {
+    let Some(object) = claims.as_object_mut() else {
+        return;
+    };
+
+    for name in TIMESTAMP_CLAIMS {
+        let Some(value) = object.get_mut(name) else {
+            continue;
+        };
+        let rendered = value
+            .as_u64()
+            .filter(|secs| *secs > 0)
+            .and_then(|secs| i64::try_from(secs).ok())
+            .and_then(|secs| DateTime::from_timestamp(secs, 0));
+        if let Some(datetime) = rendered {
+            *value = Value::String(match zone {
+                TimestampZone::Utc => datetime.to_rfc3339_opts(SecondsFormat::Secs, false),
+                TimestampZone::Local => datetime
+                    .with_timezone(&Local)
+                    .to_rfc3339_opts(SecondsFormat::Secs, false),
+            });
+        }
+    }
+}
+
+// How many levels of JSON-inside-a-string `expand_json_strings` unwraps
+const MAX_EXPANSION_DEPTH: usize = 8;
+
+/// Replaces string values that contain serialized JSON objects or arrays with the parsed value,
+/// so claims like `"metadata": "{\"role\":\"admin\"}"` become readable nested objects.
+///
+/// # Arguments
+/// * `value` - The decoded value, modified in place.
+///
+/// # Remarks
+/// Strings that are not JSON, or that parse to a bare scalar such as `"42"`, are left
+/// untouched. Expanded values are themselves expanded, up to a fixed nesting depth.
+pub fn expand_json_strings(value: &mut Value) {
+    expand_json_strings_to_depth(value, MAX_EXPANSION_DEPTH);
+}
+
+fn expand_json_strings_to_depth(value: &mut Value, depth: usize) {
+    match value {
+        Value::Object(map) => map
+            .values_mut()
+            .for_each(|child| expand_json_strings_to_depth(child, depth)),
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| expand_json_strings_to_depth(item, depth)),
+        Value::String(text) if depth > 0 => {
+            if let Ok(parsed @ (Value::Object(_) | Value::Array(_))) =
+                serde_json::from_str::<Value>(text)
+            {
+                *value = parsed;
+                expand_json_strings_to_depth(value, depth - 1);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Keeps only the named top-level claims, in the order they are named.
+///
+/// # Arguments
+/// * `claims` - The decoded payload, modified in place.
+/// * `names` - The claims to keep.
+///
+/// # Remarks
+/// Listed claims that are absent are skipped rather than added as `null`. A payload that is
+/// not an object is left untouched.
+pub fn select_claims(claims: &mut Value, names: &[String]) {
+    let Some(object) = claims.as_object_mut() else {
+        return;
+    };
+
+    *object = names
+        .iter()
+        .filter_map(|name| object.remove(name).map(|value| (name.clone(), value)))
+        .collect();
+}
+
+// What redacted values are replaced with
+const REDACTED: &str = "***";
+
+/// Masks the values of the named top-level claims, e.g. to share a decoded token without the
+/// personal data it carries.
+///
+/// # Arguments
+/// * `claims` - The decoded payload, modified in place.
+/// * `names` - The claims to mask.
+///
+/// # Remarks
+/// Each listed claim that is present is replaced with `"***"`, whatever its type; the keys
+/// themselves are kept so the shape of the payload stays visible.
+pub fn redact_claims(claims: &mut Value, names: &[String]) {
+    let Some(object) = claims.as_object_mut() else {
+        return;
+    };
+
+    for name in names {
+        if let Some(value) = object.get_mut(name) {
+            *value = Value::String(REDACTED.to_string());
+        }
+    }
+}
+
+/// Masks every string value, at any depth, with `"***"`.
+///
+/// # Arguments
+/// * `value` - The decoded value, modified in place.
+///
+/// # Remarks
+/// Numbers, booleans and nulls are left untouched, as are all object keys.
+pub fn redact_strings(value: &mut Value) {
+    match value {
+        Value::Object(map) => map.values_mut().for_each(redact_strings),
+        Value::Array(items) => items.iter_mut().for_each(redact_strings),
+        Value::String(text) => *text = REDACTED.to_string(),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_select_claims_in_requested_order() {
+        let mut claims = json!({"sub": "1234567890", "iss": "idp", "exp": 1701502400});
+        let names = ["exp", "missing", "sub"].map(String::from);
+        select_claims(&mut claims, &names);
+        assert_eq!(
+            serde_json::to_string(&claims).unwrap(),
+            r#"{"exp":1701502400,"sub":"1234567890"}"#
+        );
+    }
+
+    #[test]
+    fn test_humanize_timestamps() {
+        let mut claims = json!({"sub": "1234567890", "iat": 1516239022, "exp": 1701502400});
+        humanize_timestamps(&mut claims);
+        assert_eq!(claims["iat"], json!("2018-01-18T01:30:22+00:00"));
+        assert_eq!(claims["exp"], json!("2023-12-02T07:33:20+00:00"));
+        assert_eq!(claims["sub"], json!("1234567890"));
+    }
+
+    #[test]
+    fn test_humanize_timestamps_in_zone() {
+        let mut utc = json!({"iat": 1516239022});
+        humanize_timestamps_in(&mut utc, TimestampZone::Utc);
+        assert_eq!(utc["iat"], json!("2018-01-18T01:30:22+00:00"));
+
+        // The local rendering depends on the machine, but names the same instant.
+        let mut local = json!({"iat": 1516239022});
+        humanize_timestamps_in(&mut local, TimestampZone::Local);
+        let rendered = DateTime::parse_from_rfc3339(local["iat"].as_str().unwrap()).unwrap();
+        assert_eq!(rendered.timestamp(), 1516239022);
+    }
+
+    #[test]
+    fn test_humanize_leaves_non_positive_integers() {
+        let mut claims = json!({"iat": -5, "nbf": 0, "exp": "soon", "auth_time": 1.5});
+        let original = claims.clone();
+        humanize_timestamps(&mut claims);
+        assert_eq!(claims, original);
+    }
+
+    #[test]
+    fn test_expand_json_strings() {
+        let mut claims = json!({
+            "sub": "1234567890",
+            "metadata": "{\"role\":\"admin\",\"tags\":\"[\\\"a\\\",\\\"b\\\"]\"}",
+            "count": "42",
+            "note": "{not json",
+        });
+        expand_json_strings(&mut claims);
+        assert_eq!(
+            claims["metadata"],
+            json!({"role": "admin", "tags": ["a", "b"]})
+        );
+        assert_eq!(claims["count"], json!("42"));
+        assert_eq!(claims["note"], json!("{not json"));
+        assert_eq!(claims["sub"], json!("1234567890"));
+    }
+
+    #[test]
+    fn test_redact_claims() {
+        let mut claims = json!({"sub": "1234567890", "email": "jane@example.com"});
+        redact_claims(&mut claims, &["email".to_string(), "phone".to_string()]);
+        assert_eq!(claims, json!({"sub": "1234567890", "email": "***"}));
+    }
+
+    #[test]
+    fn test_redact_strings() {
+        let mut claims = json!({"sub": "1234567890", "exp": 100, "ctx": {"roles": ["admin"]}});
+        redact_strings(&mut claims);
+        assert_eq!(
+            claims,
+            json!({"sub": "***", "exp": 100, "ctx": {"roles": ["***"]}})
+        );
+    }
+}
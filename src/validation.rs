@@ -0,0 +1,700 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::time::Duration;
+
+use crate::decode::header_algorithm;
+use crate::{
+    decode_jwt_claims, decode_jwt_header_value, token_type, verify, JwtError, VerificationKey,
+};
+use crate::{Clock, SystemClock};
+
+// Which checks `validate` runs; checks without the needed input are skipped
+#[derive(Debug, Clone, Default)]
+pub struct ValidationOptions {
+    // Key for signature verification
+    pub key: Option<VerificationKey>,
+    // Expected `iss` claim
+    pub issuer: Option<String>,
+    // Expected entry of the `aud` claim
+    pub audience: Option<String>,
+    // Clock skew tolerated by the `exp` and `nbf` checks
+    pub leeway: Duration,
+}
+
+// Outcome of each check run by `validate`: `Some(true)` passed, `Some(false)` failed and
+// `None` was skipped (no key/expected value given, or the optional claim is absent)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ValidationReport {
+    pub signature: Option<bool>,
+    pub exp: Option<bool>,
+    pub nbf: Option<bool>,
+    pub iss: Option<bool>,
+    pub aud: Option<bool>,
+    // Why each failed check failed, when that goes beyond a simple mismatch
+    pub errors: Vec<String>,
+}
+
+impl ValidationReport {
+    /// Returns `true` if no check failed.
+    pub fn is_valid(&self) -> bool {
+        [self.signature, self.exp, self.nbf, self.iss, self.aud]
+            .iter()
+            .all(|outcome| *outcome != Some(false))
+    }
+
+    // Records the outcome of one check, keeping the error message if the check errored.
+    fn record(&mut self, check: &str, result: Result<bool, JwtError>) -> Option<bool> {
+        match result {
+            Ok(passed) => Some(passed),
+            Err(e) => {
+                self.errors.push(format!("{}: {}", check, e));
+                Some(false)
+            }
+        }
+    }
+}
+
+/// Reads a NumericDate claim (seconds since the Unix epoch) from the decoded claims.
+///
+/// Fractional values are truncated to whole seconds.
+fn timestamp_claim(claims: &Value, name: &str) -> Result<i64, JwtError> {
+    let value = claims
+        .get(name)
+        .ok_or_else(|| JwtError::MissingClaim(name.to_string()))?;
+
+    value
+        .as_i64()
+        .or_else(|| value.as_f64().map(|secs| secs as i64))
+        .ok_or_else(|| JwtError::InvalidClaimType(name.to_string()))
+}
+
+/// Returns the current system time as whole seconds since the Unix epoch.
+fn now_unix() -> i64 {
+    SystemClock.now_unix()
+}
+
+// Converts a leeway into whole seconds, saturating for absurdly large values.
+fn leeway_secs(leeway: Duration) -> i64 {
+    i64::try_from(leeway.as_secs()).unwrap_or(i64::MAX)
+}
+
+// Whether the `exp` claim places `now` past the end of the validity window.
+fn expired_at(claims: &Value, now: i64, leeway_secs: i64) -> Result<bool, JwtError> {
+    let exp = timestamp_claim(claims, "exp")?;
+    Ok(past_expiry(exp, now, leeway_secs))
+}
+
+// Whether `now` lies past `exp` and the leeway: a token is still valid at `exp` itself.
+pub(crate) fn past_expiry(exp: i64, now: i64, leeway_secs: i64) -> bool {
+    now > exp.saturating_add(leeway_secs)
+}
+
+// Whether the `nbf` claim places `now` before the start of the validity window.
+fn not_yet_valid_at(claims: &Value, now: i64, leeway_secs: i64) -> Result<bool, JwtError> {
+    let nbf = timestamp_claim(claims, "nbf")?;
+    Ok(now < nbf.saturating_sub(leeway_secs))
+}
+
+/// Checks whether the `exp` claim of a JWT lies in the past.
+///
+/// # Arguments
+/// * `token_str` - A string slice representing the JWT.
+/// * `leeway` - Clock skew tolerated after `exp`; the token counts as expired only once
+///   `now > exp + leeway`.
+/// * `clock` - The source of `now`, usually `&SystemClock`.
+///
+/// # Returns
+/// `Ok(true)` if the token has expired, `Ok(false)` if it is still valid, or a `JwtError`
+/// (`MissingClaim` when `exp` is absent, `InvalidClaimType` when it is not a number).
+///
+/// # Remarks
+/// This function does NOT verify the JWT's signature.
+pub fn is_expired(token_str: &str, leeway: Duration, clock: &dyn Clock) -> Result<bool, JwtError> {
+    let claims = decode_jwt_claims(token_str)?;
+    expired_at(&claims, clock.now_unix(), leeway_secs(leeway))
+}
+
+/// Checks whether the `nbf` (not-before) claim of a JWT lies in the future.
+///
+/// # Arguments
+/// * `token_str` - A string slice representing the JWT.
+/// * `leeway` - Clock skew tolerated before `nbf`; the token counts as not valid yet only while
+///   `now < nbf - leeway`.
+/// * `clock` - The source of `now`, usually `&SystemClock`.
+///
+/// # Returns
+/// `Ok(true)` if the token is not valid yet, `Ok(false)` if its validity window has started,
+/// or a `JwtError` (`MissingClaim` when `nbf` is absent, `InvalidClaimType` when it is not a
+/// number).
+///
+/// # Remarks
+/// This function does NOT verify the JWT's signature.
+pub fn is_not_yet_valid(
+    token_str: &str,
+    leeway: Duration,
+    clock: &dyn Clock,
+) -> Result<bool, JwtError> {
+    let claims = decode_jwt_claims(token_str)?;
+    not_yet_valid_at(&claims, clock.now_unix(), leeway_secs(leeway))
+}
+
+/// Returns how many seconds remain until the `exp` claim of a JWT is reached.
+///
+/// # Arguments
+/// * `token_str` - A string slice representing the JWT.
+///
+/// # Returns
+/// `exp - now` in seconds, negative once the token has expired, or a `JwtError`
+/// (`MissingClaim` when `exp` is absent, `InvalidClaimType` when it is not a number).
+///
+/// # Remarks
+/// This function does NOT verify the JWT's signature.
+pub fn seconds_until_expiry(token_str: &str) -> Result<i64, JwtError> {
+    let claims = decode_jwt_claims(token_str)?;
+    Ok(timestamp_claim(&claims, "exp")?.saturating_sub(now_unix()))
+}
+
+/// Returns how many seconds have passed since a JWT was issued, according to its `iat` claim.
+///
+/// # Arguments
+/// * `token_str` - A string slice representing the JWT.
+///
+/// # Returns
+/// `now - iat` in seconds, or a `JwtError` (`MissingClaim` when `iat` is absent,
+/// `InvalidClaimType` when it is not a number).
+///
+/// # Remarks
+/// An `iat` in the future, usually from clock skew between issuer and reader, gives a
+/// negative age rather than an error so the caller can decide how to treat it.
+/// This function does NOT verify the JWT's signature.
+pub fn token_age_seconds(token_str: &str) -> Result<i64, JwtError> {
+    let claims = decode_jwt_claims(token_str)?;
+    Ok(now_unix().saturating_sub(timestamp_claim(&claims, "iat")?))
+}
+
+/// Checks that the current time lies within the window given by the `exp` and `nbf` claims.
+///
+/// # Arguments
+/// * `token_str` - A string slice representing the JWT.
+/// * `leeway` - Clock skew tolerated on both ends of the window.
+///
+/// # Returns
+/// `Ok(true)` if the token is currently valid, `Ok(false)` if it has expired or is not valid
+/// yet, or a `JwtError` if a present claim is not a number.
+///
+/// # Remarks
+/// Absent `exp` or `nbf` claims place no limit on that end of the window.
+/// This function does NOT verify the JWT's signature.
+pub fn validate_time_claims(token_str: &str, leeway: Duration) -> Result<bool, JwtError> {
+    let claims = decode_jwt_claims(token_str)?;
+    let now = now_unix();
+    let leeway_secs = leeway_secs(leeway);
+
+    let expired = match expired_at(&claims, now, leeway_secs) {
+        Err(JwtError::MissingClaim(_)) => false,
+        result => result?,
+    };
+    let not_yet_valid = match not_yet_valid_at(&claims, now, leeway_secs) {
+        Err(JwtError::MissingClaim(_)) => false,
+        result => result?,
+    };
+
+    Ok(!expired && !not_yet_valid)
+}
+
+/// Checks whether a JWT was issued for the expected audience.
+///
+/// # Arguments
+/// * `token_str` - A string slice representing the JWT.
+/// * `expected` - The audience identifying this service.
+///
+/// # Returns
+/// `Ok(true)` if `aud` equals `expected` or, in its array form, contains it; `Ok(false)`
+/// otherwise; or a `JwtError` (`MissingClaim` when `aud` is absent, `InvalidClaimType` when it
+/// is neither a string nor an array of strings).
+///
+/// # Remarks
+/// This function does NOT verify the JWT's signature.
+pub fn check_audience(token_str: &str, expected: &str) -> Result<bool, JwtError> {
+    let claims = decode_jwt_claims(token_str)?;
+    audience_matches(&claims, expected)
+}
+
+// Reads the audiences from the `aud` claim, in either its string or array form.
+fn audiences(claims: &Value) -> Result<Vec<&str>, JwtError> {
+    let aud = claims
+        .get("aud")
+        .ok_or_else(|| JwtError::MissingClaim("aud".to_string()))?;
+
+    // RFC 7519 allows `aud` to be a single string or an array of strings.
+    match aud {
+        Value::String(audience) => Ok(vec![audience]),
+        Value::Array(audiences) => audiences
+            .iter()
+            .map(|audience| {
+                audience
+                    .as_str()
+                    .ok_or_else(|| JwtError::InvalidClaimType("aud".to_string()))
+            })
+            .collect(),
+        _ => Err(JwtError::InvalidClaimType("aud".to_string())),
+    }
+}
+
+// Whether the `aud` claim names `expected`.
+fn audience_matches(claims: &Value, expected: &str) -> Result<bool, JwtError> {
+    Ok(audiences(claims)?.contains(&expected))
+}
+
+/// Checks whether a JWT was issued by the expected issuer.
+///
+/// # Arguments
+/// * `token_str` - A string slice representing the JWT.
+/// * `expected` - The trusted issuer, compared exactly against `iss`.
+///
+/// # Returns
+/// `Ok(true)` if `iss` equals `expected`, `Ok(false)` otherwise, or a `JwtError`
+/// (`MissingClaim` when `iss` is absent, `InvalidClaimType` when it is not a string).
+///
+/// # Remarks
+/// This function does NOT verify the JWT's signature.
+pub fn check_issuer(token_str: &str, expected: &str) -> Result<bool, JwtError> {
+    let claims = decode_jwt_claims(token_str)?;
+    issuer_matches(&claims, expected)
+}
+
+/// Checks that a JWT carries no top-level claims beyond an allow-list, e.g. to notice when an
+/// issuer starts emitting new claims.
+///
+/// # Arguments
+/// * `token_str` - A string slice representing the JWT.
+/// * `allowed` - The expected claim names. Listed claims may also be absent.
+///
+/// # Returns
+/// `Ok(())` when every claim is listed (or the payload is not an object), otherwise
+/// `JwtError::UnexpectedClaim` naming the first unlisted claim in payload order.
+///
+/// # Remarks
+/// This function does NOT verify the JWT's signature.
+pub fn check_allowed_claims(token_str: &str, allowed: &[String]) -> Result<(), JwtError> {
+    let claims = decode_jwt_claims(token_str)?;
+    let unexpected = claims
+        .as_object()
+        .into_iter()
+        .flat_map(|object| object.keys())
+        .find(|name| !allowed.contains(name));
+    match unexpected {
+        Some(name) => Err(JwtError::UnexpectedClaim(name.clone())),
+        None => Ok(()),
+    }
+}
+
+// Whether the `iss` claim is exactly `expected`.
+fn issuer_matches(claims: &Value, expected: &str) -> Result<bool, JwtError> {
+    let iss = claims
+        .get("iss")
+        .ok_or_else(|| JwtError::MissingClaim("iss".to_string()))?
+        .as_str()
+        .ok_or_else(|| JwtError::InvalidClaimType("iss".to_string()))?;
+
+    Ok(iss == expected)
+}
+
+/// Requires the header `typ` of a JWT to name the expected media type.
+///
+/// Following RFC 7515 section 4.1.9, the comparison ignores case and an `application/`
+/// prefix, so `"JWT"` also accepts `"jwt"` and `"application/jwt"`.
+///
+/// # Arguments
+/// * `token_str` - A string slice representing the JWT.
+/// * `expected` - The required type, such as `"JWT"` or `"at+jwt"`.
+///
+/// # Returns
+/// `Ok(())` if the type matches, or a `JwtError` (`UnexpectedTokenType` naming the actual
+/// type, `MissingClaim` when the header has no `typ`).
+///
+/// # Remarks
+/// This function does NOT verify the JWT's signature.
+pub fn require_token_type(token_str: &str, expected: &str) -> Result<(), JwtError> {
+    let typ = token_type(token_str)?.ok_or_else(|| JwtError::MissingClaim("typ".to_string()))?;
+    if media_type(&typ).eq_ignore_ascii_case(media_type(expected)) {
+        Ok(())
+    } else {
+        Err(JwtError::UnexpectedTokenType(typ))
+    }
+}
+
+// Strips the optional `application/` prefix from a `typ` value
+fn media_type(typ: &str) -> &str {
+    match typ.get(.."application/".len()) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("application/") => &typ[prefix.len()..],
+        _ => typ,
+    }
+}
+
+/// Runs signature, `exp`, `nbf`, `iss` and `aud` checks on a JWT and reports each outcome.
+///
+/// # Arguments
+/// * `token_str` - A string slice representing the JWT.
+/// * `options` - The key, expected values and leeway to validate against.
+///
+/// # Returns
+/// A `ValidationReport` with one entry per check, or a `JwtError` if the payload cannot be
+/// decoded at all. Individual failing checks are reported rather than returned as errors, so
+/// every check always runs.
+pub fn validate(
+    token_str: &str,
+    options: &ValidationOptions,
+) -> Result<ValidationReport, JwtError> {
+    let claims = decode_jwt_claims(token_str)?;
+    let now = now_unix();
+    let leeway_secs = leeway_secs(options.leeway);
+    let mut report = ValidationReport::default();
+
+    if let Some(key) = &options.key {
+        report.signature = report.record("signature", verify(token_str, key));
+    }
+
+    // Absent `exp`/`nbf` claims leave that end of the window open, so the check is skipped.
+    report.exp = match expired_at(&claims, now, leeway_secs) {
+        Err(JwtError::MissingClaim(_)) => None,
+        result => report.record("exp", result.map(|expired| !expired)),
+    };
+    report.nbf = match not_yet_valid_at(&claims, now, leeway_secs) {
+        Err(JwtError::MissingClaim(_)) => None,
+        result => report.record("nbf", result.map(|not_yet_valid| !not_yet_valid)),
+    };
+
+    if let Some(issuer) = &options.issuer {
+        report.iss = report.record("iss", issuer_matches(&claims, issuer));
+    }
+    if let Some(audience) = &options.audience {
+        report.aud = report.record("aud", audience_matches(&claims, audience));
+    }
+
+    Ok(report)
+}
+
+// An at-a-glance description of a token, for dashboards and scripts. Fields whose claim is
+// absent or malformed are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct TokenSummary {
+    pub alg: Option<String>,
+    // Whether `exp` has passed
+    pub expired: Option<bool>,
+    // Seconds until `exp`, negative once expired
+    pub ttl: Option<i64>,
+    pub issuer: Option<String>,
+    pub audiences: Option<Vec<String>>,
+    // Number of top-level claims
+    pub claim_count: Option<usize>,
+}
+
+/// Summarizes a JWT's algorithm, expiry, issuer, audiences and claim count.
+///
+/// # Arguments
+/// * `token_str` - A string slice representing the JWT.
+///
+/// # Returns
+/// The `TokenSummary`, or a `JwtError` only when the header or payload cannot be decoded.
+///
+/// # Remarks
+/// The header and payload are each decoded once and every field is computed from them, using
+/// a single reading of the clock.
+/// This function does NOT verify the JWT's signature.
+pub fn summarize(token_str: &str) -> Result<TokenSummary, JwtError> {
+    let header = decode_jwt_header_value(token_str)?;
+    let claims = decode_jwt_claims(token_str)?;
+    let now = now_unix();
+
+    let exp = timestamp_claim(&claims, "exp").ok();
+    Ok(TokenSummary {
+        alg: header_algorithm(&header).ok().map(str::to_string),
+        expired: exp.map(|exp| now > exp),
+        ttl: exp.map(|exp| exp.saturating_sub(now)),
+        issuer: claims
+            .get("iss")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        audiences: audiences(&claims)
+            .ok()
+            .map(|audiences| audiences.into_iter().map(str::to_string).collect()),
+        claim_count: claims.as_object().map(|claims| claims.len()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parts::token_with_payload;
+    use crate::FixedClock;
+
+    #[test]
+    fn test_past_exp_is_expired() {
+        let token = token_with_payload(r#"{"sub":"1234567890","exp":1516239022}"#);
+        assert!(is_expired(&token, Duration::ZERO, &SystemClock).unwrap());
+    }
+
+    #[test]
+    fn test_future_exp_is_not_expired() {
+        // 4102444800 is 2100-01-01T00:00:00Z.
+        let token = token_with_payload(r#"{"sub":"1234567890","exp":4102444800}"#);
+        assert!(!is_expired(&token, Duration::ZERO, &SystemClock).unwrap());
+    }
+
+    #[test]
+    fn test_seconds_until_future_expiry() {
+        let exp = now_unix() + 3600;
+        let token = token_with_payload(&format!(r#"{{"exp":{}}}"#, exp));
+        // Allow for the clock ticking over between the two readings.
+        assert!((3599..=3600).contains(&seconds_until_expiry(&token).unwrap()));
+    }
+
+    #[test]
+    fn test_seconds_until_past_expiry_is_negative() {
+        let token = token_with_payload(r#"{"sub":"1234567890","exp":1516239022}"#);
+        assert!(seconds_until_expiry(&token).unwrap() < 0);
+
+        let token = token_with_payload(r#"{"sub":"1234567890"}"#);
+        assert_eq!(
+            seconds_until_expiry(&token).unwrap_err(),
+            JwtError::MissingClaim("exp".to_string())
+        );
+    }
+
+    #[test]
+    fn test_token_age() {
+        let iat = now_unix() - 600;
+        let token = token_with_payload(&format!(r#"{{"iat":{}}}"#, iat));
+        // Allow for the clock ticking over between the two readings.
+        assert!((600..=601).contains(&token_age_seconds(&token).unwrap()));
+
+        // Issued "in the future" because of clock skew.
+        let token = token_with_payload(&format!(r#"{{"iat":{}}}"#, now_unix() + 60));
+        assert!(token_age_seconds(&token).unwrap() < 0);
+
+        let token = token_with_payload(r#"{"sub":"1234567890"}"#);
+        assert_eq!(
+            token_age_seconds(&token).unwrap_err(),
+            JwtError::MissingClaim("iat".to_string())
+        );
+    }
+
+    #[test]
+    fn test_summarize() {
+        let token = token_with_payload(
+            r#"{"iss":"https://issuer.example.com","aud":["api","web"],"exp":1516239022}"#,
+        );
+        let summary = summarize(&token).unwrap();
+        assert_eq!(summary.alg.as_deref(), Some("HS256"));
+        assert_eq!(summary.expired, Some(true));
+        assert!(summary.ttl.unwrap() < 0);
+        assert_eq!(
+            summary.issuer.as_deref(),
+            Some("https://issuer.example.com")
+        );
+        assert_eq!(
+            summary.audiences,
+            Some(vec!["api".to_string(), "web".to_string()])
+        );
+        assert_eq!(summary.claim_count, Some(3));
+
+        // Missing claims are left empty instead of failing the summary.
+        let summary = summarize(&token_with_payload(r#"{"sub":"1234567890"}"#)).unwrap();
+        assert_eq!(
+            summary,
+            TokenSummary {
+                alg: Some("HS256".to_string()),
+                claim_count: Some(1),
+                ..TokenSummary::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_missing_exp() {
+        let token = token_with_payload(r#"{"sub":"1234567890"}"#);
+        assert_eq!(
+            is_expired(&token, Duration::ZERO, &SystemClock).unwrap_err(),
+            JwtError::MissingClaim("exp".to_string())
+        );
+    }
+
+    #[test]
+    fn test_non_numeric_exp() {
+        let token = token_with_payload(r#"{"exp":"tomorrow"}"#);
+        assert_eq!(
+            is_expired(&token, Duration::ZERO, &SystemClock).unwrap_err(),
+            JwtError::InvalidClaimType("exp".to_string())
+        );
+    }
+
+    #[test]
+    fn test_future_nbf_is_not_yet_valid() {
+        let token = token_with_payload(r#"{"nbf":4102444800}"#);
+        assert!(is_not_yet_valid(&token, Duration::ZERO, &SystemClock).unwrap());
+    }
+
+    #[test]
+    fn test_past_nbf_is_valid() {
+        let token = token_with_payload(r#"{"nbf":1516239022}"#);
+        assert!(!is_not_yet_valid(&token, Duration::ZERO, &SystemClock).unwrap());
+    }
+
+    #[test]
+    fn test_validate_time_claims() {
+        let valid = token_with_payload(r#"{"nbf":1516239022,"exp":4102444800}"#);
+        assert!(validate_time_claims(&valid, Duration::ZERO).unwrap());
+
+        let expired = token_with_payload(r#"{"nbf":1516239022,"exp":1516239022}"#);
+        assert!(!validate_time_claims(&expired, Duration::ZERO).unwrap());
+
+        // Neither claim present means there is no window to fall outside of.
+        let unbounded = token_with_payload(r#"{"sub":"1234567890"}"#);
+        assert!(validate_time_claims(&unbounded, Duration::ZERO).unwrap());
+    }
+
+    #[test]
+    fn test_validate_time_claims_leeway() {
+        let now = now_unix();
+        let token = token_with_payload(&format!(r#"{{"nbf":{}}}"#, now + 20));
+        assert!(!validate_time_claims(&token, Duration::ZERO).unwrap());
+        assert!(validate_time_claims(&token, Duration::from_secs(30)).unwrap());
+    }
+
+    #[test]
+    fn test_recently_expired_token_within_leeway() {
+        let token = token_with_payload(r#"{"exp":1000}"#);
+        let clock = FixedClock(1010);
+        assert!(is_expired(&token, Duration::ZERO, &clock).unwrap());
+        assert!(!is_expired(&token, Duration::from_secs(30), &clock).unwrap());
+    }
+
+    #[test]
+    fn test_not_yet_valid_within_leeway() {
+        let token = token_with_payload(r#"{"nbf":1000}"#);
+        let clock = FixedClock(990);
+        assert!(is_not_yet_valid(&token, Duration::ZERO, &clock).unwrap());
+        assert!(!is_not_yet_valid(&token, Duration::from_secs(30), &clock).unwrap());
+    }
+
+    #[test]
+    fn test_exact_time_boundaries() {
+        // The token is still valid in the second of its exp and from the second of its nbf.
+        let token = token_with_payload(r#"{"nbf":1000,"exp":2000}"#);
+        assert!(!is_expired(&token, Duration::ZERO, &FixedClock(2000)).unwrap());
+        assert!(is_expired(&token, Duration::ZERO, &FixedClock(2001)).unwrap());
+        assert!(!is_expired(&token, Duration::from_secs(5), &FixedClock(2005)).unwrap());
+        assert!(is_expired(&token, Duration::from_secs(5), &FixedClock(2006)).unwrap());
+
+        assert!(is_not_yet_valid(&token, Duration::ZERO, &FixedClock(999)).unwrap());
+        assert!(!is_not_yet_valid(&token, Duration::ZERO, &FixedClock(1000)).unwrap());
+    }
+
+    #[test]
+    fn test_audience_string_form() {
+        let token = token_with_payload(r#"{"aud":"svc"}"#);
+        assert!(check_audience(&token, "svc").unwrap());
+        assert!(!check_audience(&token, "other").unwrap());
+    }
+
+    #[test]
+    fn test_audience_array_form() {
+        let token = token_with_payload(r#"{"aud":["svc1","svc2"]}"#);
+        assert!(check_audience(&token, "svc2").unwrap());
+        assert!(!check_audience(&token, "svc3").unwrap());
+    }
+
+    #[test]
+    fn test_audience_missing_or_invalid() {
+        let token = token_with_payload(r#"{"sub":"1234567890"}"#);
+        assert_eq!(
+            check_audience(&token, "svc").unwrap_err(),
+            JwtError::MissingClaim("aud".to_string())
+        );
+
+        let token = token_with_payload(r#"{"aud":42}"#);
+        assert_eq!(
+            check_audience(&token, "svc").unwrap_err(),
+            JwtError::InvalidClaimType("aud".to_string())
+        );
+    }
+
+    #[test]
+    fn test_issuer_exact_match() {
+        let token = token_with_payload(r#"{"iss":"https://issuer.example.com"}"#);
+        assert!(check_issuer(&token, "https://issuer.example.com").unwrap());
+        assert!(!check_issuer(&token, "https://issuer.example.com/").unwrap());
+    }
+
+    #[test]
+    fn test_issuer_not_a_string() {
+        let token = token_with_payload(r#"{"iss":["https://issuer.example.com"]}"#);
+        assert_eq!(
+            check_issuer(&token, "https://issuer.example.com").unwrap_err(),
+            JwtError::InvalidClaimType("iss".to_string())
+        );
+    }
+
+    #[test]
+    fn test_allowed_claims() {
+        let token = token_with_payload(r#"{"sub":"1","iss":"idp","internal_id":7,"debug":true}"#);
+        let allowed = ["sub", "iss", "exp"].map(String::from);
+        assert_eq!(
+            check_allowed_claims(&token, &allowed).unwrap_err(),
+            JwtError::UnexpectedClaim("internal_id".to_string())
+        );
+
+        let allowed = ["sub", "iss", "internal_id", "debug"].map(String::from);
+        assert!(check_allowed_claims(&token, &allowed).is_ok());
+        assert!(check_allowed_claims(&token_with_payload("[1]"), &[]).is_ok());
+    }
+
+    #[test]
+    fn test_require_token_type() {
+        // Header {"alg":"HS256","typ":"at+jwt"}
+        let token = "eyJhbGciOiJIUzI1NiIsInR5cCI6ImF0K2p3dCJ9.e30.c2ln";
+        assert!(require_token_type(token, "at+jwt").is_ok());
+        assert!(require_token_type(token, "application/AT+JWT").is_ok());
+        assert_eq!(
+            require_token_type(token, "JWT").unwrap_err(),
+            JwtError::UnexpectedTokenType("at+jwt".to_string())
+        );
+
+        // Header {"alg":"HS256"}
+        assert_eq!(
+            require_token_type("eyJhbGciOiJIUzI1NiJ9.e30.c2ln", "JWT").unwrap_err(),
+            JwtError::MissingClaim("typ".to_string())
+        );
+    }
+
+    #[test]
+    fn test_validate_reports_each_check() {
+        let token = token_with_payload(
+            r#"{"iss":"https://issuer.example.com","aud":["svc"],"exp":4102444800}"#,
+        );
+        let options = ValidationOptions {
+            key: Some(VerificationKey::Hmac(b"your-256-bit-secret".to_vec())),
+            issuer: Some("https://issuer.example.com".to_string()),
+            audience: Some("other-svc".to_string()),
+            leeway: Duration::from_secs(30),
+        };
+        let report = validate(&token, &options).unwrap();
+
+        // The helper token carries a dummy signature, so verification fails.
+        assert_eq!(report.signature, Some(false));
+        assert_eq!(report.exp, Some(true));
+        assert_eq!(report.nbf, None);
+        assert_eq!(report.iss, Some(true));
+        assert_eq!(report.aud, Some(false));
+        assert!(!report.is_valid());
+    }
+
+    #[test]
+    fn test_validate_with_no_options_skips_checks() {
+        let token = token_with_payload(r#"{"sub":"1234567890"}"#);
+        let report = validate(&token, &ValidationOptions::default()).unwrap();
+        assert_eq!(report, ValidationReport::default());
+        assert!(report.is_valid());
+    }
+}